@@ -0,0 +1,76 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::world::blocks::Blocks;
+
+/// index into the `ItemRegistry`
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct ItemId(pub u32);
+
+#[derive(Clone, Debug)]
+pub struct ItemDef {
+    pub name: String,
+    pub max_stack: u32,
+    /// block this item puts into the world when placed
+    pub places: Option<Blocks>,
+    /// mining speed multiplier while the item is selected, bare hands are 1
+    pub tool_power: Option<f32>,
+}
+
+/// every item in the game, blocks get one placeable item each
+#[derive(Resource, Debug)]
+pub struct ItemRegistry {
+    items: Vec<ItemDef>,
+    by_block: HashMap<Blocks, ItemId>,
+}
+
+impl Default for ItemRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            items: Vec::new(),
+            by_block: HashMap::default(),
+        };
+
+        for block in Blocks::ALL {
+            // blocks that can't be mined can't be held either
+            if block.hardness().is_none() { continue; }
+            registry.register(ItemDef {
+                name: block.tile_name().to_string(),
+                max_stack: 999,
+                places: Some(block),
+                tool_power: None,
+            });
+        }
+
+        registry
+    }
+}
+
+impl ItemRegistry {
+    pub fn register(&mut self, def: ItemDef) -> ItemId {
+        let id = ItemId(self.items.len() as u32);
+        if let Some(block) = def.places {
+            self.by_block.insert(block, id);
+        }
+        self.items.push(def);
+        id
+    }
+
+    #[inline]
+    pub fn get(&self, id: ItemId) -> Option<&ItemDef> {
+        self.items.get(id.0 as usize)
+    }
+
+    /// item a block turns into when it's broken
+    #[inline]
+    pub fn item_for_block(&self, block: Blocks) -> Option<ItemId> {
+        self.by_block.get(&block).copied()
+    }
+
+    pub fn max_stack(&self, id: ItemId) -> u32 {
+        self.get(id).map_or(1, |def| def.max_stack)
+    }
+
+    pub fn name(&self, id: ItemId) -> &str {
+        self.get(id).map_or("???", |def| def.name.as_str())
+    }
+}
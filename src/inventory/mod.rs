@@ -0,0 +1,245 @@
+pub mod items;
+
+use bevy::{prelude::*, input::mouse::MouseWheel};
+
+use crate::physics::{Collider, PhysicsBundle, PhysicsPosition};
+use crate::player::health::{Dead, Difficulty, PlayerDied};
+use crate::player::player::Player;
+use crate::states::GameState;
+
+pub use items::{ItemId, ItemRegistry};
+
+pub const HOTBAR_SIZE: usize = 10;
+pub const INVENTORY_SIZE: usize = 40;
+
+/// size of a dropped item in the world, in pixels
+const DROPPED_ITEM_SIZE: Vec2 = Vec2::new(4.0, 4.0);
+/// seconds before a dropped item can be picked up, so dropping things doesn't instantly undo itself
+const PICKUP_DELAY: f32 = 1.0;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct ItemStack {
+    pub item: ItemId,
+    pub count: u32,
+}
+
+impl ItemStack {
+    pub fn new(item: ItemId, count: u32) -> Self {
+        Self { item, count }
+    }
+}
+
+/// slots `0..HOTBAR_SIZE` are the hotbar
+#[derive(Component, Clone, Debug)]
+pub struct Inventory {
+    pub slots: Vec<Option<ItemStack>>,
+    /// hotbar slot in use
+    pub selected: usize,
+}
+
+impl Inventory {
+    pub fn new(size: usize) -> Self {
+        Self {
+            slots: vec![None; size],
+            selected: 0,
+        }
+    }
+
+    /// tops up matching stacks first, then fills empty slots. returns whatever didn't fit
+    pub fn insert(&mut self, mut stack: ItemStack, registry: &ItemRegistry) -> Option<ItemStack> {
+        let max_stack = registry.max_stack(stack.item);
+
+        for slot in self.slots.iter_mut().flatten() {
+            if slot.item != stack.item || slot.count >= max_stack { continue; }
+            let moved = (max_stack - slot.count).min(stack.count);
+            slot.count += moved;
+            stack.count -= moved;
+            if stack.count == 0 { return None; }
+        }
+
+        for slot in self.slots.iter_mut() {
+            if slot.is_some() { continue; }
+            let moved = max_stack.min(stack.count);
+            *slot = Some(ItemStack::new(stack.item, moved));
+            stack.count -= moved;
+            if stack.count == 0 { return None; }
+        }
+
+        Some(stack)
+    }
+
+    #[inline]
+    pub fn selected_stack(&self) -> Option<ItemStack> {
+        self.slots[self.selected]
+    }
+
+    /// takes up to `count` items out of `slot`, emptying it when it runs out
+    pub fn take(&mut self, slot: usize, count: u32) -> Option<ItemStack> {
+        let stack = self.slots[slot].as_mut()?;
+        let taken = ItemStack::new(stack.item, stack.count.min(count));
+        stack.count -= taken.count;
+        if stack.count == 0 {
+            self.slots[slot] = None;
+        }
+        Some(taken)
+    }
+
+    /// puts `stack` into `slot`, merging with a matching stack there or swapping it out otherwise.
+    /// returns what's left in hand afterwards
+    pub fn place(&mut self, slot: usize, stack: ItemStack, registry: &ItemRegistry) -> Option<ItemStack> {
+        match self.slots[slot] {
+            Some(mut existing) if existing.item == stack.item => {
+                let room = registry.max_stack(stack.item).saturating_sub(existing.count);
+                let moved = room.min(stack.count);
+                existing.count += moved;
+                self.slots[slot] = Some(existing);
+                (stack.count > moved).then(|| ItemStack::new(stack.item, stack.count - moved))
+            },
+            existing => {
+                self.slots[slot] = Some(stack);
+                existing
+            },
+        }
+    }
+
+    /// empties the inventory, handing back everything that was in it
+    pub fn drain(&mut self) -> Vec<ItemStack> {
+        self.slots.iter_mut().filter_map(Option::take).collect()
+    }
+}
+
+/// an item stack lying in the world, waiting to be picked up
+#[derive(Component, Debug)]
+pub struct DroppedItem {
+    pub stack: ItemStack,
+    pickup_delay: Timer,
+}
+
+/// whether the inventory screen is open, and the stack being moved around in it
+#[derive(Resource, Debug, Default)]
+pub struct InventoryScreen {
+    pub open: bool,
+    pub held: Option<ItemStack>,
+}
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ItemRegistry>();
+        app.init_resource::<InventoryScreen>();
+
+        app.add_systems((
+            select_hotbar,
+            toggle_inventory_screen,
+            pick_up_items,
+            drop_inventory_on_death,
+        ).in_set(OnUpdate(GameState::InGame)));
+    }
+}
+
+/// spawns `stack` as an item in the world at `position`, it falls and collides like anything else
+pub fn drop_stack(commands: &mut Commands, position: Vec2, stack: ItemStack) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(1.0, 0.8, 0.2),
+                ..default()
+            },
+            transform: Transform {
+                translation: position.extend(15.0),
+                scale: DROPPED_ITEM_SIZE.extend(1.0),
+                ..default()
+            },
+            ..default()
+        },
+        PhysicsBundle::new(position, DROPPED_ITEM_SIZE),
+        DroppedItem {
+            stack,
+            pickup_delay: Timer::from_seconds(PICKUP_DELAY, TimerMode::Once),
+        },
+    ));
+}
+
+pub fn select_hotbar(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut scroll_evr: EventReader<MouseWheel>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+) {
+    const KEYS: [KeyCode; HOTBAR_SIZE] = [
+        KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5,
+        KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9, KeyCode::Key0,
+    ];
+
+    let mut inventory = player_query.single_mut();
+    if let Some(slot) = KEYS.iter().position(|key| keyboard_input.just_pressed(*key)) {
+        inventory.selected = slot;
+    }
+
+    for ev in scroll_evr.iter() {
+        // scrolling down moves right, like most games
+        let step = if ev.y < 0.0 { 1 } else if ev.y > 0.0 { HOTBAR_SIZE - 1 } else { 0 };
+        inventory.selected = (inventory.selected + step) % HOTBAR_SIZE;
+    }
+}
+
+pub fn toggle_inventory_screen(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut screen: ResMut<InventoryScreen>,
+    mut player_query: Query<(&Transform, &mut Inventory), With<Player>>,
+    registry: Res<ItemRegistry>,
+    mut commands: Commands,
+) {
+    if !keyboard_input.just_pressed(KeyCode::E) { return; }
+
+    screen.open = !screen.open;
+    if screen.open { return; }
+
+    // put back whatever was still held, dropping it if there's no room
+    let Some(held) = screen.held.take() else { return };
+    let (transform, mut inventory) = player_query.single_mut();
+    if let Some(leftover) = inventory.insert(held, &registry) {
+        drop_stack(&mut commands, transform.translation.truncate(), leftover);
+    }
+}
+
+pub fn pick_up_items(
+    mut commands: Commands,
+    mut item_query: Query<(Entity, &mut DroppedItem, &PhysicsPosition, &Collider)>,
+    mut player_query: Query<(&PhysicsPosition, &Collider, &mut Inventory), (With<Player>, Without<Dead>)>,
+    registry: Res<ItemRegistry>,
+    time: Res<Time>,
+) {
+    let Ok((player_position, player_collider, mut inventory)) = player_query.get_single_mut() else { return };
+    let player_rect = Rect::from_center_size(player_position.current, player_collider.0);
+
+    for (entity, mut dropped, position, collider) in item_query.iter_mut() {
+        if !dropped.pickup_delay.tick(time.delta()).finished() { continue; }
+        let item_rect = Rect::from_center_size(position.current, collider.0);
+        if player_rect.intersect(item_rect).is_empty() { continue; }
+
+        match inventory.insert(dropped.stack, &registry) {
+            Some(leftover) => dropped.stack = leftover,
+            None => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+/// scatters the inventory where the player died, unless the difficulty lets them keep it
+pub fn drop_inventory_on_death(
+    mut commands: Commands,
+    mut died_events: EventReader<PlayerDied>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    difficulty: Res<Difficulty>,
+) {
+    for died in died_events.iter() {
+        if difficulty.keeps_inventory() { continue; }
+
+        let mut inventory = player_query.single_mut();
+        for (i, stack) in inventory.drain().into_iter().enumerate() {
+            // spread the drops out a little so they don't all land in one pile
+            let offset = Vec2::new((i % 5) as f32 * 2.0 - 4.0, 0.0);
+            drop_stack(&mut commands, died.position + offset, stack);
+        }
+    }
+}
@@ -6,6 +6,8 @@ use states::GameState;
 
 mod states;
 mod player;
+mod physics;
+mod inventory;
 mod world;
 mod ui;
 
@@ -29,9 +31,11 @@ pub fn app() -> App {
     );
 
     app.add_state::<GameState>();
-    app.add_loading_state(LoadingState::new(GameState::AssetLoading).continue_to_state(GameState::WorldGeneration));
+    app.add_loading_state(LoadingState::new(GameState::AssetLoading).continue_to_state(GameState::AssetValidation));
     
+    app.add_plugin(physics::PhysicsPlugin::default());
     app.add_plugin(player::PlayerPlugin);
+    app.add_plugin(inventory::InventoryPlugin);
     app.add_plugin(world::WorldPlugin);
     app.add_plugin(ui::UIPlugin);
 
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use bevy_tileset::prelude::Tilesets;
+
+use crate::states::GameState;
+use crate::world::WorldStorage;
+use crate::world::collision::{self, Contacts};
+
+/// gameplay that feeds into physics, like player input, runs `before(PhysicsSet::Step)`
+/// and anything following a body's transform runs `after(PhysicsSet::Interpolate)`
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    /// gravity and movement, in `CoreSchedule::FixedUpdate`
+    Step,
+    /// transforms catching up to the physics step, every frame
+    Interpolate,
+}
+
+/// in pixels per second
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Velocity(pub Vec2);
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Gravity {
+    /// in pixels per second squared
+    pub acceleration: f32,
+    /// fastest a body can fall, in pixels per second
+    pub terminal_velocity: f32,
+}
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Self {
+            acceleration: 900.0,
+            terminal_velocity: 480.0,
+        }
+    }
+}
+
+/// size of a body's box in pixels, centered on its position
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Collider(pub Vec2);
+
+/// on bodies that were standing on something after the last physics tick
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Grounded;
+
+/// where the physics step puts a body, `Transform` gets interpolated between ticks from this
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PhysicsPosition {
+    pub previous: Vec2,
+    pub current: Vec2,
+}
+
+impl PhysicsPosition {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            previous: position,
+            current: position,
+        }
+    }
+
+    /// moves the body without interpolating from where it was
+    pub fn teleport(&mut self, position: Vec2) {
+        self.previous = position;
+        self.current = position;
+    }
+}
+
+/// everything a body needs to fall and collide with tiles, leave `Gravity` out for floating bodies
+#[derive(Bundle)]
+pub struct PhysicsBundle {
+    pub position: PhysicsPosition,
+    pub velocity: Velocity,
+    pub gravity: Gravity,
+    pub collider: Collider,
+    pub contacts: Contacts,
+}
+
+impl PhysicsBundle {
+    pub fn new(position: Vec2, size: Vec2) -> Self {
+        Self {
+            position: PhysicsPosition::new(position),
+            velocity: Velocity::default(),
+            gravity: Gravity::default(),
+            collider: Collider(size),
+            contacts: Contacts::default(),
+        }
+    }
+}
+
+pub struct PhysicsPlugin {
+    /// physics ticks per second
+    pub tick_rate: f32,
+}
+
+impl Default for PhysicsPlugin {
+    fn default() -> Self {
+        Self { tick_rate: 60.0 }
+    }
+}
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FixedTime::new_from_secs(1.0 / self.tick_rate));
+
+        app.add_systems((
+            apply_gravity,
+            move_bodies,
+        ).chain()
+            .in_set(PhysicsSet::Step)
+            .distributive_run_if(in_state(GameState::InGame))
+            .in_schedule(CoreSchedule::FixedUpdate));
+
+        app.add_system(interpolate_transforms
+            .in_set(PhysicsSet::Interpolate)
+            .in_set(OnUpdate(GameState::InGame)));
+    }
+}
+
+pub fn apply_gravity(
+    mut query: Query<(&mut Velocity, &Gravity)>,
+    fixed_time: Res<FixedTime>,
+) {
+    let delta = fixed_time.period.as_secs_f32();
+    for (mut velocity, gravity) in query.iter_mut() {
+        velocity.0.y = (velocity.0.y - gravity.acceleration * delta).max(-gravity.terminal_velocity);
+    }
+}
+
+/// moves every body by its velocity, sweeping it against the world's tiles
+pub fn move_bodies(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut PhysicsPosition, &Collider, &mut Velocity, &mut Contacts)>,
+    world_storage: Res<WorldStorage>,
+    tilesets: Tilesets,
+    fixed_time: Res<FixedTime>,
+) {
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let delta = fixed_time.period.as_secs_f32();
+
+    for (entity, mut position, collider, mut velocity, mut contacts) in query.iter_mut() {
+        let aabb = Rect::from_center_size(position.current, collider.0);
+        let sweep = collision::sweep(&world_storage, aabb, velocity.0 * delta, tile_size);
+        position.previous = position.current;
+        position.current = sweep.aabb.center();
+        velocity.0 = sweep.contacts.clip(velocity.0);
+
+        if sweep.contacts.grounded != contacts.grounded {
+            if sweep.contacts.grounded {
+                commands.entity(entity).insert(Grounded);
+            } else {
+                commands.entity(entity).remove::<Grounded>();
+            }
+        }
+        *contacts = sweep.contacts;
+    }
+}
+
+/// blends transforms between the last two physics ticks by however far into the next one we are,
+/// so motion stays smooth when the frame rate doesn't match the tick rate
+pub fn interpolate_transforms(
+    mut query: Query<(&PhysicsPosition, &mut Transform)>,
+    fixed_time: Res<FixedTime>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / fixed_time.period.as_secs_f32()).min(1.0);
+    for (position, mut transform) in query.iter_mut() {
+        let interpolated = position.previous.lerp(position.current, alpha);
+        transform.translation.x = interpolated.x;
+        transform.translation.y = interpolated.y;
+    }
+}
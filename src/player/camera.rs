@@ -1,10 +1,14 @@
 use bevy::{prelude::*, core_pipeline::clear_color::ClearColorConfig, input::mouse::MouseWheel, window::PrimaryWindow};
 use bevy_tileset::prelude::Tilesets;
 
-use crate::world::{LoadPoint, WorldStorage};
-use crate::world::blocks::Blocks;
-use crate::world::chunks::{self, RenderedChunks};
+use crate::inventory::{Inventory, InventoryScreen, ItemRegistry};
+use crate::physics::{Collider, PhysicsPosition};
+use crate::world::{LoadPoint, ViewLoadArea, WorldStorage};
+use crate::world::blocks::{Blocks, Walls};
+use crate::world::chunks::TileChanged;
+use crate::world::coordinates::{TilePos, WorldPos};
 
+use super::health::Dead;
 use super::player::Player;
 
 #[derive(Component)]
@@ -22,13 +26,14 @@ pub fn spawn(
     camera_bundle.camera_2d.clear_color = ClearColorConfig::Custom(Color::rgb(71./255., 209./255., 1.));
     let tileset = tilesets.get_by_name("world_tiles").unwrap();
     let tile_size = tileset.tile_size();
-    let spawn_point = world.get_spawn_point();
-    camera_bundle.transform.translation.x = spawn_point.x as f32 * tile_size.x;
-    camera_bundle.transform.translation.y = spawn_point.y as f32 * tile_size.y;
+    let spawn_point = TilePos(world.get_spawn_point().as_ivec2()).center(tile_size);
+    camera_bundle.transform.translation.x = spawn_point.0.x;
+    camera_bundle.transform.translation.y = spawn_point.0.y;
 
     commands.spawn((
         camera_bundle,
-        LoadPoint::new(4),
+        LoadPoint::new(1),
+        ViewLoadArea::new(1),
     ));
 }
 
@@ -63,35 +68,63 @@ pub fn movement(
     }
 }
 
-pub fn mouse(
-    commands: Commands,
+/// tile under the cursor, if the cursor is over the window
+pub(super) fn cursor_tile(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+    tile_size: Vec2,
+) -> Option<TilePos> {
+    let (camera, camera_transform) = camera_query.single();
+    let primary = window_query.get_single().ok()?;
+
+    primary
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
+        .map(|ray| WorldPos(ray.origin.truncate()).to_tile(tile_size))
+}
+
+/// right click puts the selected hotbar item's block down at the cursor
+pub fn place(
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     buttons: Res<Input<MouseButton>>,
     mut world: ResMut<WorldStorage>,
     tilesets: Tilesets,
-    rendered_chunks: ResMut<RenderedChunks>
+    mut tile_changed_events: EventWriter<TileChanged>,
+    mut player_query: Query<(&mut Inventory, &PhysicsPosition, &Collider), (With<Player>, Without<Dead>)>,
+    registry: Res<ItemRegistry>,
+    screen: Res<InventoryScreen>,
 ) {
-    if !buttons.just_pressed(MouseButton::Left) { return }
+    if !buttons.just_pressed(MouseButton::Right) || screen.open { return }
+    let Ok((mut inventory, position, collider)) = player_query.get_single_mut() else { return };
+    let Some(stack) = inventory.selected_stack() else { return };
+    let Some(block) = registry.get(stack.item).and_then(|def| def.places) else { return };
 
-    let (camera, camera_transform) = camera_query.single();
-    let Ok(primary) = window_query.get_single() else { return };
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let Some(tile_pos) = cursor_tile(&window_query, &camera_query, tile_size) else { return };
+    let IVec2 { x, y } = tile_pos.0;
+    if !world.in_bounds(x, y) || world.get_tile(x, y) != Blocks::Air { return; }
 
-    if let Some(world_position) = primary
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
-        .map(|ray| ray.origin.truncate())
-    {
-        let tileset = tilesets.get_by_name("world_tiles").unwrap();
-        let tile_pos = (world_position.as_ivec2() + 4) / 8;
-        if !world.in_bounds(tile_pos.x, tile_pos.y) { return; }
-
-        
-        world.set_tile(tile_pos.x, tile_pos.y, Blocks::Air);
-        let chunk_pos = chunks::camera_pos_to_chunk_pos(world_position, tileset.tile_size());
-        info!("clicking at tile:{tile_pos}, chunk:{chunk_pos}");
-        chunks::dirty_rendered_chunk(commands, &chunk_pos, rendered_chunks);
-    }
+    // don't trap the player inside their own block
+    let tile_min = tile_pos.min(tile_size).0;
+    let tile_rect = Rect::from_corners(tile_min, tile_min + tile_size);
+    let player_rect = Rect::from_center_size(position.current, collider.0);
+    if block.collision().is_collidable() && !player_rect.intersect(tile_rect).is_empty() { return; }
+
+    // blocks have to be attached to something
+    let anchored = world.get_wall(x, y) != Walls::Air
+        || [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].iter().any(|offset| {
+            let neighbour = tile_pos.0 + *offset;
+            world.in_bounds(neighbour.x, neighbour.y)
+                && (world.get_tile(neighbour.x, neighbour.y) != Blocks::Air
+                    || world.get_wall(neighbour.x, neighbour.y) != Walls::Air)
+        });
+    if !anchored { return; }
+
+    let selected = inventory.selected;
+    inventory.take(selected, 1);
+    world.set_tile(x, y, block);
+    tile_changed_events.send(TileChanged(tile_pos));
 }
 
 pub fn follow_player(
@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+
+use crate::physics::Velocity;
+use crate::world::collision::Contacts;
+
+use super::player::Player;
+use super::respawn::RespawnPlayer;
+
+/// decides what dying costs, the inventory reads `keeps_inventory` when `PlayerDied` is sent
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    /// the inventory is kept on death
+    Casual,
+    /// the inventory is dropped where the player died
+    #[default]
+    Normal,
+}
+
+impl Difficulty {
+    pub fn keeps_inventory(&self) -> bool {
+        *self == Self::Casual
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct HealthConfig {
+    /// landing slower than this is free, in pixels per second
+    pub safe_fall_speed: f32,
+    /// damage per pixel per second landed above `safe_fall_speed`
+    pub fall_damage: f32,
+    /// seconds between dying and respawning
+    pub respawn_delay: f32,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            safe_fall_speed: 320.0,
+            fall_damage: 0.5,
+            respawn_delay: 5.0,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+    /// healed per second, once `regeneration_delay` seconds have passed without taking damage
+    pub regeneration: f32,
+    pub regeneration_delay: f32,
+    since_damage: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            regeneration: 1.0,
+            regeneration_delay: 8.0,
+            since_damage: 0.0,
+        }
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        if amount <= 0.0 { return; }
+        self.current = (self.current - amount).max(0.0);
+        self.since_damage = 0.0;
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    #[inline]
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// 0 to 1, for health bars
+    #[inline]
+    pub fn fraction(&self) -> f32 {
+        self.current / self.max
+    }
+}
+
+/// how fast the player was falling going into the current physics tick
+#[derive(Component, Debug, Default)]
+pub struct FallSpeed(f32);
+
+/// on the player while they're waiting to respawn
+#[derive(Component, Debug)]
+pub struct Dead {
+    pub respawn_timer: Timer,
+}
+
+/// sent once when the player dies, `position` is where the inventory gets dropped
+pub struct PlayerDied {
+    pub position: Vec2,
+}
+
+/// runs before the physics step, since landing zeroes the velocity we need
+pub fn record_fall_speed(
+    mut player_query: Query<(&Velocity, &mut FallSpeed), With<Player>>,
+) {
+    for (velocity, mut fall_speed) in player_query.iter_mut() {
+        fall_speed.0 = (-velocity.0.y).max(0.0);
+    }
+}
+
+/// runs after the physics step, hurts the player for landing faster than `safe_fall_speed`
+pub fn apply_fall_damage(
+    mut player_query: Query<(&Contacts, &FallSpeed, &mut Health), (With<Player>, Without<Dead>)>,
+    config: Res<HealthConfig>,
+) {
+    for (contacts, fall_speed, mut health) in player_query.iter_mut() {
+        if !contacts.grounded || fall_speed.0 <= config.safe_fall_speed { continue; }
+        health.damage((fall_speed.0 - config.safe_fall_speed) * config.fall_damage);
+    }
+}
+
+pub fn regenerate(
+    mut player_query: Query<&mut Health, (With<Player>, Without<Dead>)>,
+    time: Res<Time>,
+) {
+    for mut health in player_query.iter_mut() {
+        health.since_damage += time.delta_seconds();
+        if health.since_damage < health.regeneration_delay { continue; }
+        let amount = health.regeneration * time.delta_seconds();
+        health.heal(amount);
+    }
+}
+
+pub fn check_death(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &Health, &Transform, &mut Visibility), (With<Player>, Without<Dead>)>,
+    config: Res<HealthConfig>,
+    mut died_events: EventWriter<PlayerDied>,
+) {
+    for (entity, health, transform, mut visibility) in player_query.iter_mut() {
+        if !health.is_dead() { continue; }
+
+        *visibility = Visibility::Hidden;
+        commands.entity(entity).insert(Dead {
+            respawn_timer: Timer::from_seconds(config.respawn_delay, TimerMode::Once),
+        });
+        died_events.send(PlayerDied { position: transform.translation.truncate() });
+        info!("player died");
+    }
+}
+
+pub fn tick_respawn_timer(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Dead, &mut Health, &mut Visibility), With<Player>>,
+    time: Res<Time>,
+    mut respawn_events: EventWriter<RespawnPlayer>,
+) {
+    for (entity, mut dead, mut health, mut visibility) in player_query.iter_mut() {
+        if !dead.respawn_timer.tick(time.delta()).finished() { continue; }
+
+        health.current = health.max;
+        *visibility = Visibility::Inherited;
+        commands.entity(entity).remove::<Dead>();
+        respawn_events.send(RespawnPlayer);
+    }
+}
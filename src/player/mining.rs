@@ -0,0 +1,138 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_tileset::prelude::Tilesets;
+
+use crate::inventory::{self, Inventory, InventoryScreen, ItemRegistry, ItemStack};
+use crate::world::WorldStorage;
+use crate::world::blocks::Blocks;
+use crate::world::chunks::TileChanged;
+use crate::world::coordinates::TilePos;
+
+use super::camera;
+use super::health::Dead;
+use super::player::Player;
+
+/// mining speed with nothing useful selected
+const HAND_POWER: f32 = 1.0;
+const CRACK_STAGES: u32 = 4;
+
+/// the tile the player is working on and how far along they are, from 0 to 1
+#[derive(Component, Debug, Default)]
+pub struct Mining {
+    pub target: Option<TilePos>,
+    pub progress: f32,
+}
+
+impl Mining {
+    fn reset(&mut self) {
+        self.target = None;
+        self.progress = 0.0;
+    }
+
+    /// 0 until the first crack shows up, then up to `CRACK_STAGES`
+    pub fn crack_stage(&self) -> u32 {
+        (self.progress * (CRACK_STAGES + 1) as f32).floor() as u32
+    }
+}
+
+/// darkens the tile being mined a step per crack stage, stands in for crack textures until there's art for them
+#[derive(Component)]
+pub struct CrackOverlay;
+
+pub fn spawn_crack_overlay(
+    mut commands: Commands,
+    tilesets: Tilesets,
+) {
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::NONE,
+                custom_size: Some(tile_size),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CrackOverlay,
+    ));
+}
+
+/// holding left click mines the tile under the cursor, taking the block's hardness
+/// divided by the selected item's tool power. moving to another tile starts over
+pub fn mine(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    buttons: Res<Input<MouseButton>>,
+    mut world: ResMut<WorldStorage>,
+    tilesets: Tilesets,
+    mut tile_changed_events: EventWriter<TileChanged>,
+    mut player_query: Query<(&mut Mining, &mut Inventory, Option<&Dead>), With<Player>>,
+    registry: Res<ItemRegistry>,
+    screen: Res<InventoryScreen>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let Ok((mut mining, mut inventory, dead)) = player_query.get_single_mut() else { return };
+    // dying drops whatever was being mined, so the overlay goes away and nothing carries over
+    if dead.is_some() || !buttons.pressed(MouseButton::Left) || screen.open {
+        mining.reset();
+        return;
+    }
+
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let Some(tile_pos) = camera::cursor_tile(&window_query, &camera_query, tile_size) else {
+        mining.reset();
+        return;
+    };
+    if mining.target != Some(tile_pos) {
+        mining.target = Some(tile_pos);
+        mining.progress = 0.0;
+    }
+
+    let IVec2 { x, y } = tile_pos.0;
+    let block = if world.in_bounds(x, y) { world.get_tile(x, y) } else { Blocks::Air };
+    let Some(hardness) = block.hardness() else {
+        mining.progress = 0.0;
+        return;
+    };
+
+    let power = inventory
+        .selected_stack()
+        .and_then(|stack| registry.get(stack.item))
+        .and_then(|def| def.tool_power)
+        .unwrap_or(HAND_POWER);
+    mining.progress += power * time.delta_seconds() / hardness;
+    if mining.progress < 1.0 { return; }
+
+    world.set_tile(x, y, Blocks::Air);
+    if let Some(item) = registry.item_for_block(block) {
+        if let Some(leftover) = inventory.insert(ItemStack::new(item, 1), &registry) {
+            inventory::drop_stack(&mut commands, tile_pos.center(tile_size).0, leftover);
+        }
+    }
+    mining.reset();
+    tile_changed_events.send(TileChanged(tile_pos));
+}
+
+pub fn update_crack_overlay(
+    player_query: Query<&Mining, With<Player>>,
+    mut overlay_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<CrackOverlay>>,
+    tilesets: Tilesets,
+) {
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let (mut transform, mut sprite, mut visibility) = overlay_query.single_mut();
+
+    let Some((tile_pos, stage)) = player_query
+        .get_single()
+        .ok()
+        .and_then(|mining| Some((mining.target?, mining.crack_stage())))
+        .filter(|(_, stage)| *stage > 0)
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    *visibility = Visibility::Inherited;
+    transform.translation = tile_pos.center(tile_size).0.extend(10.0);
+    sprite.color = Color::rgba(0.0, 0.0, 0.0, stage as f32 / (CRACK_STAGES + 1) as f32);
+}
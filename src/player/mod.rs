@@ -1,7 +1,12 @@
 mod camera;
 pub mod player;
+pub mod movement;
+pub mod respawn;
+pub mod health;
+pub mod mining;
 
 use bevy::prelude::*;
+use crate::physics::PhysicsSet;
 use crate::states::GameState;
 
 pub struct PlayerPlugin;
@@ -10,16 +15,39 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems((
             player::spawn,
-            camera::spawn
+            camera::spawn,
+            mining::spawn_crack_overlay
         ).in_schedule(OnEnter(GameState::InGame)));
 
+        app.init_resource::<movement::PlayerMovementConfig>();
+        app.init_resource::<health::HealthConfig>();
+        app.init_resource::<health::Difficulty>();
+        app.add_event::<respawn::RespawnPlayer>();
+        app.add_event::<health::PlayerDied>();
         app.add_systems((
-            player::update_gravity,
-            // player::update_translation,
-            player::check_for_collisions,
-            player::update_translation,
-            camera::follow_player, 
-            camera::mouse
+            movement::update_movement,
+            health::record_fall_speed,
+        ).before(PhysicsSet::Step)
+            .distributive_run_if(in_state(GameState::InGame))
+            .in_schedule(CoreSchedule::FixedUpdate));
+        app.add_system(health::apply_fall_damage
+            .after(PhysicsSet::Step)
+            .run_if(in_state(GameState::InGame))
+            .in_schedule(CoreSchedule::FixedUpdate));
+
+        app.add_system(camera::follow_player
+            .after(PhysicsSet::Interpolate)
+            .in_set(OnUpdate(GameState::InGame)));
+        app.add_systems((
+            movement::read_input,
+            mining::mine,
+            mining::update_crack_overlay.after(mining::mine),
+            camera::place,
+            respawn::check_out_of_world,
+            respawn::respawn.after(respawn::check_out_of_world).after(health::tick_respawn_timer),
+            health::regenerate,
+            health::check_death,
+            health::tick_respawn_timer,
         ).in_set(OnUpdate(GameState::InGame)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::physics::{Grounded, Velocity};
+
+use super::health::Dead;
+use super::player::Player;
+
+/// everything that decides how the player feels to control, speeds are in pixels per second
+#[derive(Resource, Clone, Debug)]
+pub struct PlayerMovementConfig {
+    pub max_speed: f32,
+    /// how fast the player gets up to `max_speed` while holding a direction
+    pub acceleration: f32,
+    /// how fast the player stops on the ground once nothing is held
+    pub friction: f32,
+    pub air_acceleration: f32,
+    pub air_friction: f32,
+    pub jump_velocity: f32,
+    /// upwards velocity is multiplied by this when jump is let go early, for short hops
+    pub jump_cut: f32,
+    /// seconds after walking off a ledge a jump still counts
+    pub coyote_time: f32,
+    /// seconds before landing a jump press gets remembered for
+    pub jump_buffer: f32,
+}
+
+impl Default for PlayerMovementConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: 96.0,
+            acceleration: 900.0,
+            friction: 1200.0,
+            air_acceleration: 600.0,
+            air_friction: 200.0,
+            jump_velocity: 300.0,
+            jump_cut: 0.5,
+            coyote_time: 0.1,
+            jump_buffer: 0.1,
+        }
+    }
+}
+
+/// input gathered every frame, held until the next physics tick reads it
+#[derive(Component, Debug, Default)]
+pub struct PlayerInput {
+    /// -1 to 1
+    pub horizontal: f32,
+    pub jump_held: bool,
+    /// stays set until a tick consumes it, so presses between ticks aren't lost
+    pub jump_pressed: bool,
+}
+
+/// jump timers, counted down every physics tick
+#[derive(Component, Debug, Default)]
+pub struct JumpState {
+    coyote: f32,
+    buffer: f32,
+    /// rising from a jump that hasn't been cut yet
+    jumping: bool,
+}
+
+pub fn read_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut player_query: Query<&mut PlayerInput, With<Player>>,
+) {
+    let mut input = player_query.single_mut();
+
+    let left = keyboard_input.any_pressed([KeyCode::A, KeyCode::Left]);
+    let right = keyboard_input.any_pressed([KeyCode::D, KeyCode::Right]);
+    input.horizontal = right as i32 as f32 - left as i32 as f32;
+
+    let jump_keys = [KeyCode::Space, KeyCode::W, KeyCode::Up];
+    input.jump_held = keyboard_input.any_pressed(jump_keys);
+    if keyboard_input.any_just_pressed(jump_keys) {
+        input.jump_pressed = true;
+    }
+}
+
+pub fn update_movement(
+    mut player_query: Query<(&mut PlayerInput, &mut JumpState, &mut Velocity, Option<&Grounded>), (With<Player>, Without<Dead>)>,
+    config: Res<PlayerMovementConfig>,
+    fixed_time: Res<FixedTime>,
+) {
+    let delta = fixed_time.period.as_secs_f32();
+    // dead players don't get to move
+    let Ok((mut input, mut jump, mut velocity, grounded)) = player_query.get_single_mut() else { return };
+    let grounded = grounded.is_some();
+
+    // walking
+    let (acceleration, friction) = if grounded {
+        (config.acceleration, config.friction)
+    } else {
+        (config.air_acceleration, config.air_friction)
+    };
+    velocity.0.x = if input.horizontal != 0.0 {
+        move_towards(velocity.0.x, input.horizontal * config.max_speed, acceleration * delta)
+    } else {
+        move_towards(velocity.0.x, 0.0, friction * delta)
+    };
+
+    // jumping
+    if std::mem::take(&mut input.jump_pressed) {
+        jump.buffer = config.jump_buffer;
+    }
+    if grounded {
+        jump.coyote = config.coyote_time;
+        jump.jumping = false;
+    }
+
+    if jump.buffer > 0.0 && jump.coyote > 0.0 {
+        velocity.0.y = config.jump_velocity;
+        jump.buffer = 0.0;
+        jump.coyote = 0.0;
+        jump.jumping = true;
+    } else if jump.jumping && !input.jump_held {
+        if velocity.0.y > 0.0 {
+            velocity.0.y *= config.jump_cut;
+        }
+        jump.jumping = false;
+    }
+
+    jump.buffer = (jump.buffer - delta).max(0.0);
+    jump.coyote = (jump.coyote - delta).max(0.0);
+}
+
+#[inline]
+fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    current + (target - current).clamp(-max_delta, max_delta)
+}
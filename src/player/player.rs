@@ -1,22 +1,45 @@
-use bevy::math::{vec2, Vec3Swizzles};
-use bevy::{prelude::*, math::vec3};
-use bevy::sprite::collide_aabb::{collide};
+use bevy::prelude::*;
+use bevy_tileset::prelude::Tilesets;
 
+use crate::inventory::{Inventory, ItemRegistry, ItemStack, INVENTORY_SIZE};
+use crate::physics::PhysicsBundle;
 use crate::world::WorldStorage;
-use crate::world::chunks::{GlobalTilePos, Collidable, self, RenderedChunks};
+use crate::world::blocks::Blocks;
 
-#[derive(Component)]
-pub struct Velocity(pub Vec2);
+use super::health::{FallSpeed, Health};
+use super::mining::Mining;
+use super::movement::{JumpState, PlayerInput};
+use super::respawn::{self, SpawnPoint};
 
-#[derive(Component)]
-pub struct Gravity(f32);
+const PLAYER_SIZE: Vec2 = Vec2::new(8.0, 16.0);
+
+/// blocks a new player gets, world generation doesn't place any of these
+const STARTING_ITEMS: [(Blocks, u32); 4] = [
+    (Blocks::WoodPlatform, 100),
+    (Blocks::StoneSlab, 100),
+    (Blocks::StoneSlopeUp, 50),
+    (Blocks::StoneSlopeDown, 50),
+];
 
 #[derive(Component)]
 pub struct Player;
 
 pub fn spawn(
-    mut commands: Commands
+    mut commands: Commands,
+    world_storage: Res<WorldStorage>,
+    tilesets: Tilesets,
+    item_registry: Res<ItemRegistry>,
 ) {
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let spawn_point = SpawnPoint::default();
+    let position = respawn::standing_position(spawn_point.resolve(&world_storage), tile_size, PLAYER_SIZE);
+
+    let mut inventory = Inventory::new(INVENTORY_SIZE);
+    for (block, count) in STARTING_ITEMS {
+        let Some(item) = item_registry.item_for_block(block) else { continue };
+        inventory.insert(ItemStack::new(item, count), &item_registry);
+    }
+
     commands.spawn((
         SpriteBundle {
             sprite: Sprite {
@@ -24,66 +47,20 @@ pub fn spawn(
                 ..default()
             },
             transform: Transform {
-                translation: vec3(100.0, 1900.0, 20.0),
-                scale: vec3(8.0, 16.0, 8.0),
+                translation: position.extend(20.0),
+                scale: PLAYER_SIZE.extend(8.0),
                 ..default()
             },
             ..default()
         },
-        Velocity(Vec2::ZERO),
-        Gravity(1.0),
+        PhysicsBundle::new(position, PLAYER_SIZE),
+        spawn_point,
+        Health::new(100.0),
+        FallSpeed::default(),
+        inventory,
+        Mining::default(),
+        PlayerInput::default(),
+        JumpState::default(),
         Player
     ));
 }
-
-pub fn update_gravity(
-    mut player_query: Query<(&mut Velocity, &Gravity), With<Player>>
-) {
-    let (mut velocity, gravity) = player_query.single_mut();
-    velocity.0.y = -gravity.0;
-}
-
-pub fn update_translation(
-    mut player_query: Query<(&Velocity, &mut Transform), With<Player>>
-) {
-    let (velocity, mut transform) = player_query.single_mut();
-    transform.translation.x += velocity.0.x;
-    transform.translation.y += velocity.0.y;
-}
-
-pub fn check_for_collisions(
-    mut player_query: Query<(&Transform, &mut Velocity), With<Player>>,
-    tile_query: Query<&GlobalTilePos, With<Collidable>>
-) {
-    let (player_transform, mut player_velocity) = player_query.single_mut();
-    for tile_pos in tile_query.iter() {
-        let collision = collide(
-            player_transform.translation, 
-            player_transform.scale.truncate(),
-            vec3(tile_pos.0.x as f32 * 8.0, tile_pos.0.y as f32 * 8.0, player_transform.translation.z),
-            vec2(8.0, 8.0)
-        );
-
-        if let Some(_did_collide) = collision {
-            info!("COLLIDE!!");
-            player_velocity.0 = Vec2::ZERO;
-        } else {
-            // info!("nofin :(");
-        }
-    }
-}
-
-pub fn create_colls(
-    player_query: Query<&Transform, With<Player>>,
-    mut commands: Commands,
-    rendered_chunks: Res<RenderedChunks>,
-    world_storage: Res<WorldStorage>,
-) {
-    let player_transform = player_query.single();
-    chunks::make_coll(
-        player_transform.translation.xy(),
-        commands,
-        rendered_chunks,
-        world_storage
-    );
-}
\ No newline at end of file
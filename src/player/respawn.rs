@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_tileset::prelude::Tilesets;
+
+use crate::physics::{Collider, PhysicsPosition, Velocity};
+use crate::world::WorldStorage;
+use crate::world::coordinates::TilePos;
+
+use super::health::Dead;
+use super::player::Player;
+
+/// how far below the bottom of the world the player can fall before getting respawned, in tiles
+const FALL_OUT_DEPTH: f32 = 32.0;
+
+/// a personal spawn point (e.g. from a bed) that wins over the world's, as long as it's still usable
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct SpawnPoint(pub Option<TilePos>);
+
+impl SpawnPoint {
+    #[allow(dead_code)]
+    pub fn set(&mut self, tile_pos: TilePos) {
+        self.0 = Some(tile_pos);
+    }
+
+    /// go back to the world's spawn point
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// tile the player should appear on, personal spawn points inside terrain get ignored
+    pub fn resolve(&self, world: &WorldStorage) -> TilePos {
+        self.0
+            .filter(|tile_pos| {
+                world.in_bounds(tile_pos.0.x, tile_pos.0.y)
+                    && !world.get_tile(tile_pos.0.x, tile_pos.0.y).collision().is_collidable()
+            })
+            .unwrap_or_else(|| TilePos(world.get_spawn_point().as_ivec2()))
+    }
+}
+
+/// send to put the player back at their spawn point
+pub struct RespawnPlayer;
+
+/// world position a body of `size` stands at when its feet are on the bottom of `tile_pos`
+pub fn standing_position(tile_pos: TilePos, tile_size: Vec2, size: Vec2) -> Vec2 {
+    let min = tile_pos.min(tile_size).0;
+    Vec2::new(min.x + tile_size.x / 2.0, min.y + size.y / 2.0)
+}
+
+pub fn respawn(
+    mut respawn_events: EventReader<RespawnPlayer>,
+    mut player_query: Query<(&mut PhysicsPosition, &mut Velocity, &Collider, &SpawnPoint), With<Player>>,
+    world_storage: Res<WorldStorage>,
+    tilesets: Tilesets,
+) {
+    if respawn_events.iter().count() == 0 { return; }
+
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let (mut position, mut velocity, collider, spawn_point) = player_query.single_mut();
+    let tile_pos = spawn_point.resolve(&world_storage);
+
+    position.teleport(standing_position(tile_pos, tile_size, collider.0));
+    velocity.0 = Vec2::ZERO;
+    info!("respawned player at tile {}", tile_pos.0);
+}
+
+pub fn check_out_of_world(
+    player_query: Query<&PhysicsPosition, (With<Player>, Without<Dead>)>,
+    tilesets: Tilesets,
+    mut respawn_events: EventWriter<RespawnPlayer>,
+) {
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let Ok(position) = player_query.get_single() else { return };
+    let bottom = TilePos::new(0, 0).min(tile_size).0.y - FALL_OUT_DEPTH * tile_size.y;
+
+    if position.current.y < bottom {
+        respawn_events.send(RespawnPlayer);
+    }
+}
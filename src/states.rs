@@ -4,6 +4,7 @@ use bevy::prelude::States;
 pub enum GameState {
     #[default]
     AssetLoading,
+    AssetValidation,
     WorldGeneration,
     InGame,
 }
\ No newline at end of file
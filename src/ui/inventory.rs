@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::inventory::{Inventory, InventoryScreen, ItemRegistry, ItemStack, HOTBAR_SIZE};
+use crate::player::player::Player;
+
+const SLOT_SIZE: f32 = 48.0;
+
+fn slot_label(stack: Option<ItemStack>, registry: &ItemRegistry) -> String {
+    match stack {
+        Some(stack) => format!("{}\n{}", registry.name(stack.item), stack.count),
+        None => String::new(),
+    }
+}
+
+fn slot_button(ui: &mut egui::Ui, label: String, selected: bool) -> egui::Response {
+    ui.add_sized(
+        [SLOT_SIZE, SLOT_SIZE],
+        egui::SelectableLabel::new(selected, egui::RichText::new(label).small())
+    )
+}
+
+pub fn hotbar(
+    mut contexts: EguiContexts,
+    player_query: Query<&Inventory, With<Player>>,
+    registry: Res<ItemRegistry>,
+    screen: Res<InventoryScreen>,
+) {
+    // the inventory screen already shows the hotbar
+    if screen.open { return; }
+    let inventory = player_query.single();
+
+    egui::Area::new("hotbar")
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -8.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for slot in 0..HOTBAR_SIZE {
+                    slot_button(ui, slot_label(inventory.slots[slot], &registry), slot == inventory.selected);
+                }
+            });
+        });
+}
+
+/// click a slot to pick its stack up, click another to put it down, merging or swapping
+pub fn inventory_screen(
+    mut contexts: EguiContexts,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    registry: Res<ItemRegistry>,
+    mut screen: ResMut<InventoryScreen>,
+) {
+    if !screen.open { return; }
+    let mut inventory = player_query.single_mut();
+    let mut clicked = None;
+
+    egui::Window::new("inventory")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Grid::new("inventory_slots").spacing([4.0, 4.0]).show(ui, |ui| {
+                for slot in 0..inventory.slots.len() {
+                    let label = slot_label(inventory.slots[slot], &registry);
+                    if slot_button(ui, label, slot == inventory.selected).clicked() {
+                        clicked = Some(slot);
+                    }
+                    if slot % HOTBAR_SIZE == HOTBAR_SIZE - 1 {
+                        ui.end_row();
+                    }
+                }
+            });
+
+            ui.separator();
+            ui.label(format!("holding: {}", slot_label(screen.held, &registry).replace('\n', " x")));
+        });
+
+    let Some(slot) = clicked else { return };
+    screen.held = match screen.held.take() {
+        Some(held) => inventory.place(slot, held, &registry),
+        None => inventory.slots[slot].take(),
+    };
+}
@@ -1,10 +1,11 @@
-use std::ops::{Add, Mul};
+mod inventory;
 
-use bevy::{prelude::*, math::{vec3, ivec2}};
+use bevy::{prelude::*, math::vec3};
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 use bevy_prototype_debug_lines::*;
+use bevy_tileset::prelude::Tilesets;
 
-use crate::{states::GameState, player::player::{Player, Velocity}, world::chunks::{Collidable, GlobalTilePos}};
+use crate::{states::GameState, physics::Velocity, player::{player::Player, health::{Health, Dead}}, world::{WorldStorage, collision::{self, Contacts}}};
 
 pub struct UIPlugin;
 
@@ -15,33 +16,81 @@ impl Plugin for UIPlugin {
         
         app.add_systems((
             ui_example,
-            draw_collideables
+            draw_collideables,
+            health_bar,
+            death_screen,
+            inventory::hotbar,
+            inventory::inventory_screen
         ).in_set(OnUpdate(GameState::InGame)));
     }
 }
 
 fn ui_example(
     mut contexts: EguiContexts,
-    player_query: Query<(&Transform, &Velocity), With<Player>>
+    player_query: Query<(&Transform, &Velocity, &Contacts), With<Player>>
 ) {
-    let (transform, velocity) = player_query.single();
+    let (transform, velocity, contacts) = player_query.single();
 
     egui::Window::new("astatine debug shit").show(contexts.ctx_mut(), |ui| {
         ui.label(format!("position: {}", transform.translation));
         ui.label(format!("velocity: {}", velocity.0));
+        ui.label(format!("contacts: {:?}", contacts));
     });
 }
 
+fn health_bar(
+    mut contexts: EguiContexts,
+    player_query: Query<&Health, With<Player>>
+) {
+    let health = player_query.single();
+
+    egui::Area::new("health_bar")
+        .anchor(egui::Align2::RIGHT_TOP, [-8.0, 8.0])
+        .show(contexts.ctx_mut(), |ui| {
+            ui.add(
+                egui::ProgressBar::new(health.fraction())
+                    .desired_width(200.0)
+                    .text(format!("{:.0} / {:.0}", health.current, health.max))
+            );
+        });
+}
+
+fn death_screen(
+    mut contexts: EguiContexts,
+    player_query: Query<&Dead, With<Player>>
+) {
+    let Ok(dead) = player_query.get_single() else { return };
+    let remaining = dead.respawn_timer.duration().as_secs_f32() - dead.respawn_timer.elapsed_secs();
+
+    egui::Window::new("you died")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(format!("respawning in {:.0}...", remaining.ceil()));
+        });
+}
+
+/// draws the collidable tiles around the player, straight out of `WorldStorage`
 fn draw_collideables(
     mut lines: ResMut<DebugLines>,
-    query: Query<&GlobalTilePos, With<Collidable>>
+    tilesets: Tilesets,
+    world_storage: Res<WorldStorage>,
+    player_query: Query<&Transform, With<Player>>
 ) {
-    for tile_pos in query.iter() {
-        let pos = tile_pos.0.mul(ivec2(8, 8)).add(ivec2(-4, -4));
-        let a = vec3(pos.x as f32, pos.y as f32, 10.0);
-        let b = vec3(pos.x as f32 + 8.0, pos.y as f32, 10.0);
-        let c = vec3(pos.x as f32, pos.y as f32 + 8.0, 10.0);
-        let d = vec3(pos.x as f32 + 8.0, pos.y as f32 + 8.0, 10.0);
+    let tile_size = tilesets.get_by_name("world_tiles").unwrap().tile_size();
+    let player_transform = player_query.single();
+    let area = Rect::from_center_size(
+        player_transform.translation.truncate(),
+        player_transform.scale.truncate() + tile_size * 4.0,
+    );
+
+    for tile in collision::overlapping_tiles(&world_storage, area, tile_size) {
+        let pos = tile.bounds.min;
+        let a = vec3(pos.x, pos.y, 10.0);
+        let b = vec3(pos.x + tile_size.x, pos.y, 10.0);
+        let c = vec3(pos.x, pos.y + tile_size.y, 10.0);
+        let d = vec3(pos.x + tile_size.x, pos.y + tile_size.y, 10.0);
         lines.line_colored(a, b, 0.0, Color::GREEN);
         lines.line_colored(b, d, 0.0, Color::GREEN);
         lines.line_colored(c, d, 0.0, Color::GREEN);
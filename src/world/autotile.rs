@@ -0,0 +1,77 @@
+use bevy::prelude::IVec2;
+
+pub const N: u8 = 1 << 0;
+pub const NE: u8 = 1 << 1;
+pub const E: u8 = 1 << 2;
+pub const SE: u8 = 1 << 3;
+pub const S: u8 = 1 << 4;
+pub const SW: u8 = 1 << 5;
+pub const W: u8 = 1 << 6;
+pub const NW: u8 = 1 << 7;
+
+pub const SHAPE_COUNT: usize = 47;
+/// shape with every neighbour connected, it renders as the block's own tile
+pub const INTERIOR: usize = SHAPE_COUNT - 1;
+
+const NEIGHBOURS: [(IVec2, u8); 8] = [
+    (IVec2 { x: 0, y: 1 }, N),
+    (IVec2 { x: 1, y: 1 }, NE),
+    (IVec2 { x: 1, y: 0 }, E),
+    (IVec2 { x: 1, y: -1 }, SE),
+    (IVec2 { x: 0, y: -1 }, S),
+    (IVec2 { x: -1, y: -1 }, SW),
+    (IVec2 { x: -1, y: 0 }, W),
+    (IVec2 { x: -1, y: 1 }, NW),
+];
+
+const SHAPES: [u8; 256] = build_shapes();
+
+/// mask of the neighbours around `pos` that `connects` to
+pub fn neighbour_mask<F>(pos: IVec2, connects: F) -> u8
+where
+    F: Fn(IVec2) -> bool,
+{
+    NEIGHBOURS
+        .iter()
+        .filter(|(offset, _)| connects(pos + *offset))
+        .fold(0, |mask, (_, bit)| mask | bit)
+}
+
+/// edge/corner/inner shape in `0..SHAPE_COUNT` for a neighbour mask.
+/// corners only count when both edges next to them connect too, which leaves 47 shapes,
+/// numbered by ascending mask. a block's texture for shape `i` is `"{tile_name}_{i}"`
+/// (`INTERIOR` excepted), `assets/tiles/AUTOTILE.md` lists every shape for whoever draws them
+#[inline]
+pub fn shape(mask: u8) -> usize {
+    SHAPES[mask as usize] as usize
+}
+
+const fn reduce(mask: u8) -> u8 {
+    let mut reduced = mask;
+    if mask & N == 0 || mask & E == 0 { reduced &= !NE; }
+    if mask & S == 0 || mask & E == 0 { reduced &= !SE; }
+    if mask & S == 0 || mask & W == 0 { reduced &= !SW; }
+    if mask & N == 0 || mask & W == 0 { reduced &= !NW; }
+    reduced
+}
+
+const fn build_shapes() -> [u8; 256] {
+    let mut indices = [0u8; 256];
+    let mut next = 0;
+    let mut mask = 0;
+    while mask < 256 {
+        if reduce(mask as u8) == mask as u8 {
+            indices[mask] = next;
+            next += 1;
+        }
+        mask += 1;
+    }
+
+    let mut shapes = [0u8; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        shapes[mask] = indices[reduce(mask as u8) as usize];
+        mask += 1;
+    }
+    shapes
+}
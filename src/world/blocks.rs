@@ -6,17 +6,171 @@ pub enum Blocks {
     Dirt,
     Stone,
     Border,
+    WoodPlatform,
+    StoneSlab,
+    StoneSlopeUp,
+    StoneSlopeDown,
+    Bedrock,
 }
 
 impl Blocks {
+    pub const ALL: [Blocks; 10] = [
+        Self::Air,
+        Self::Grass,
+        Self::Dirt,
+        Self::Stone,
+        Self::Border,
+        Self::WoodPlatform,
+        Self::StoneSlab,
+        Self::StoneSlopeUp,
+        Self::StoneSlopeDown,
+        Self::Bedrock,
+    ];
+
     pub fn from(block: u32) -> Self {
+        Self::try_from_id(block).unwrap_or(Self::Air)
+    }
+
+    pub fn try_from_id(block: u32) -> Option<Self> {
         match block {
-            0 => Self::Air,
-            1 => Self::Grass,
-            2 => Self::Dirt,
-            3 => Self::Stone,
-            4 => Self::Border,
-            _ => Self::Air,
+            0 => Some(Self::Air),
+            1 => Some(Self::Grass),
+            2 => Some(Self::Dirt),
+            3 => Some(Self::Stone),
+            4 => Some(Self::Border),
+            5 => Some(Self::WoodPlatform),
+            6 => Some(Self::StoneSlab),
+            7 => Some(Self::StoneSlopeUp),
+            8 => Some(Self::StoneSlopeDown),
+            9 => Some(Self::Bedrock),
+            _ => None,
+        }
+    }
+
+    /// name of the tile in `world_tiles.ron` this block renders as
+    pub fn tile_name(&self) -> &'static str {
+        match self {
+            Self::Air => "Air",
+            Self::Grass => "Grass",
+            Self::Dirt => "Dirt",
+            Self::Stone => "Stone",
+            Self::Border => "WorldBorder",
+            Self::WoodPlatform => "WoodPlatform",
+            Self::StoneSlab => "StoneSlab",
+            Self::StoneSlopeUp => "StoneSlopeUp",
+            Self::StoneSlopeDown => "StoneSlopeDown",
+            Self::Bedrock => "Bedrock",
+        }
+    }
+
+    /// extra tiles in `world_tiles.ron` picked alongside `tile_name` by tile position,
+    /// to break up large runs of the same block
+    pub fn variants(&self) -> &'static [&'static str] {
+        match self {
+            Self::Dirt => &["DirtPebbles", "DirtRoots"],
+            Self::Stone => &["StoneCracked", "StoneSpeckled"],
+            Self::Air | Self::Grass | Self::Border
+            | Self::WoodPlatform | Self::StoneSlab | Self::StoneSlopeUp | Self::StoneSlopeDown
+            | Self::Bedrock => &[],
+        }
+    }
+
+    /// whether the block picks edge and corner textures from its neighbours
+    pub fn autotiles(&self) -> bool {
+        matches!(self, Self::Grass | Self::Dirt | Self::Stone)
+    }
+
+    /// seconds it takes to mine the block by hand, `None` if it can't be mined at all
+    pub fn hardness(&self) -> Option<f32> {
+        match self {
+            Self::Air | Self::Border | Self::Bedrock => None,
+            Self::Grass => Some(0.4),
+            Self::Dirt => Some(0.5),
+            Self::Stone => Some(1.2),
+            Self::WoodPlatform => Some(0.3),
+            Self::StoneSlab | Self::StoneSlopeUp | Self::StoneSlopeDown => Some(0.8),
+        }
+    }
+
+    pub fn collision(&self) -> BlockCollision {
+        match self {
+            Self::Air => BlockCollision::Passable,
+            Self::Grass | Self::Dirt | Self::Stone | Self::Border | Self::Bedrock => BlockCollision::Solid,
+            Self::WoodPlatform => BlockCollision::Platform,
+            Self::StoneSlab => BlockCollision::HalfSlab,
+            Self::StoneSlopeUp => BlockCollision::SlopeUp,
+            Self::StoneSlopeDown => BlockCollision::SlopeDown,
         }
     }
-}
\ No newline at end of file
+}
+
+/// how a block collides with physics bodies
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum BlockCollision {
+    /// full tile box
+    Solid,
+    /// passes through entirely (foliage, decorations)
+    Passable,
+    /// only stops bodies falling onto it from above
+    Platform,
+    /// bottom half of the tile
+    HalfSlab,
+    /// rises from bottom left to top right
+    SlopeUp,
+    /// rises from bottom right to top left
+    SlopeDown,
+}
+
+impl BlockCollision {
+    #[inline]
+    pub fn is_collidable(&self) -> bool {
+        *self != Self::Passable
+    }
+
+    /// top of the solid part at `local_x` (0.0 = left edge, 1.0 = right edge),
+    /// as a fraction of the tile height measured from its bottom
+    pub fn surface(&self, local_x: f32) -> Option<f32> {
+        let local_x = local_x.clamp(0.0, 1.0);
+        match self {
+            Self::Passable => None,
+            Self::Solid | Self::Platform => Some(1.0),
+            Self::HalfSlab => Some(0.5),
+            Self::SlopeUp => Some(local_x),
+            Self::SlopeDown => Some(1.0 - local_x),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+#[repr(u32)]
+pub enum Walls {
+    Air,
+    DirtNatural,
+    Stone,
+}
+
+impl Walls {
+    pub const ALL: [Walls; 3] = [Self::Air, Self::DirtNatural, Self::Stone];
+
+    pub fn from(wall: u32) -> Self {
+        Self::try_from_id(wall).unwrap_or(Self::Air)
+    }
+
+    pub fn try_from_id(wall: u32) -> Option<Self> {
+        match wall {
+            0 => Some(Self::Air),
+            1 => Some(Self::DirtNatural),
+            2 => Some(Self::Stone),
+            _ => None,
+        }
+    }
+
+    /// name of the tile in `world_walls.ron` this wall renders as
+    pub fn tile_name(&self) -> &'static str {
+        match self {
+            Self::Air => "Air",
+            Self::DirtNatural => "DirtNatural",
+            Self::Stone => "Stone",
+        }
+    }
+}
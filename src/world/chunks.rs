@@ -1,27 +1,159 @@
-use bevy::{prelude::*, utils::HashMap, math::{Vec3Swizzles, Vec2Swizzles, ivec2, vec2}};
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::{HashMap, HashSet}, math::{Vec3Swizzles, ivec2, uvec2}};
 use bevy_ecs_tilemap::{tiles::*, prelude::{TilemapId, TilemapTexture}, TilemapBundle};
+use bevy::tasks::{AsyncComputeTaskPool, Task};
 use bevy_tileset::prelude::*;
+use futures_lite::future;
 
-use super::{storage::WorldStorage, blocks::Blocks};
-
-const CHUNK_SIZE: UVec2 = UVec2 { x: 64, y: 64 };
-const I_CHUNK_SIZE: IVec2 = IVec2 {
-    x: CHUNK_SIZE.x as i32,
-    y: CHUNK_SIZE.y as i32,
-};
+use super::blocks::Blocks;
+use super::coordinates::{self, ChunkPos, WorldPos, CHUNK_SIZE};
+use super::storage::{TileView, WorldRegion, WorldStorage};
+use super::textures::{BlockTextures, TileTexture};
 pub(super) const RENDER_CHUNK_SIZE: UVec2 = UVec2 {
     x: CHUNK_SIZE.x * 2,
     y: CHUNK_SIZE.y * 2,
 };
 
+/// extra chunks a load point keeps loaded past its load radius by default
+const UNLOAD_MARGIN: u32 = 2;
+
+/// keeps the chunks around its entity loaded, any number of entities can have one.
+/// chunks load within `load_radius` and only unload once they're past `unload_radius`,
+/// so walking back and forth over the edge doesn't rebuild the same chunks
 #[derive(Component, Clone, Copy, Debug)]
 pub struct LoadPoint {
-    radius: u32,
+    /// chunks loaded on each side of the center chunk, per axis
+    load_radius: UVec2,
+    unload_radius: UVec2,
 }
 
 impl LoadPoint {
     pub fn new(radius: u32) -> Self {
-        Self { radius }
+        Self::from_radius(UVec2::splat(radius))
+    }
+
+    pub fn from_radius(load_radius: UVec2) -> Self {
+        Self {
+            load_radius,
+            unload_radius: load_radius + UNLOAD_MARGIN,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_unload_radius(mut self, unload_radius: UVec2) -> Self {
+        self.unload_radius = unload_radius.max(self.load_radius);
+        self
+    }
+
+    /// changes the load radius, keeping however far past it chunks are allowed to stay loaded
+    pub fn set_load_radius(&mut self, load_radius: UVec2) {
+        let margin = self.unload_radius - self.load_radius;
+        self.load_radius = load_radius;
+        self.unload_radius = load_radius + margin;
+    }
+
+    #[inline]
+    fn covers(radius: UVec2, center: IVec2, chunk_pos: IVec2) -> bool {
+        let offset = (chunk_pos - center).abs();
+        offset.x <= radius.x as i32 && offset.y <= radius.y as i32
+    }
+}
+
+/// sizes the entity's `LoadPoint` to its camera's visible area, plus `margin` chunks
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ViewLoadArea {
+    pub margin: u32,
+}
+
+impl ViewLoadArea {
+    pub fn new(margin: u32) -> Self {
+        Self { margin }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct ChunkLoadingConfig {
+    /// most chunks spawned in a single frame, the rest wait for the next ones
+    pub spawn_budget: usize,
+    /// most chunks being built on the async compute pool at once
+    pub max_pending: usize,
+    /// what gets rendered past the edges of `WorldStorage`
+    pub boundary: WorldBoundary,
+    /// how many unloaded chunks `ChunkCache` keeps around
+    pub cache_capacity: usize,
+}
+
+impl Default for ChunkLoadingConfig {
+    fn default() -> Self {
+        Self {
+            spawn_budget: 2,
+            max_pending: 16,
+            boundary: WorldBoundary::Empty,
+            cache_capacity: 32,
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub enum WorldBoundary {
+    /// no tiles outside the world, chunks fully outside it aren't spawned at all
+    Empty,
+    /// outside the world is filled with border tiles
+    Border,
+    /// outside the world is drawn as a flat `VOID_COLOR`
+    Void,
+}
+
+const VOID_COLOR: Color = Color::BLACK;
+
+/// a single tile entity to spawn
+#[derive(Debug, Clone, Copy)]
+struct ChunkTile {
+    texture: TileTexture,
+    color: Color,
+}
+
+/// tiles of a chunk, built off the main thread by `build_chunk_data`.
+/// `None` where no tile entity should be spawned. spawned chunks keep theirs
+/// until a tile in them changes, so it can go into `ChunkCache` once they unload
+#[derive(Component, Debug, Clone)]
+pub struct ChunkData {
+    tiles: Vec<Option<ChunkTile>>,
+}
+
+/// chunks queued by `queue_chunks` that aren't spawned yet
+#[derive(Resource, Default)]
+pub struct PendingChunks {
+    tasks: HashMap<IVec2, Task<ChunkData>>,
+    ready: HashMap<IVec2, ChunkData>,
+}
+
+impl PendingChunks {
+    pub fn contains(&self, chunk_pos: &IVec2) -> bool {
+        self.tasks.contains_key(chunk_pos) || self.ready.contains_key(chunk_pos)
+    }
+
+    /// drops the chunk's queued data, it gets rebuilt from the current world next frame
+    pub fn invalidate(&mut self, chunk_pos: &IVec2) {
+        self.tasks.remove(chunk_pos);
+        self.ready.remove(chunk_pos);
+    }
+
+    /// moves finished tasks over to `ready`
+    fn poll(&mut self) {
+        let mut finished: Vec<(IVec2, ChunkData)> = Vec::new();
+        for (chunk_pos, task) in self.tasks.iter_mut() {
+            if let Some(chunk_data) = future::block_on(future::poll_once(task)) {
+                finished.push((*chunk_pos, chunk_data));
+            }
+        }
+
+        for (chunk_pos, chunk_data) in finished {
+            self.tasks.remove(&chunk_pos);
+            self.ready.insert(chunk_pos, chunk_data);
+        }
     }
 }
 
@@ -30,85 +162,286 @@ pub struct RenderedChunks {
     loaded: HashMap<IVec2, Entity>,
 }
 
-#[derive(Component)]
-pub struct Dirty;
+impl RenderedChunks {
+    /// tilemap entity of a spawned chunk
+    #[inline]
+    pub fn get(&self, chunk_pos: &IVec2) -> Option<Entity> {
+        self.loaded.get(chunk_pos).copied()
+    }
 
-#[derive(Component)]
-pub struct ChunkPos(IVec2);
+    #[inline]
+    pub fn contains(&self, chunk_pos: &IVec2) -> bool {
+        self.loaded.contains_key(chunk_pos)
+    }
 
-#[derive(Component)]
-pub struct Collidable;
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, Entity)> + '_ {
+        self.loaded.iter().map(|(chunk_pos, entity)| (*chunk_pos, *entity))
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.loaded.len()
+    }
+
+    #[allow(dead_code)]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty()
+    }
+}
+
+/// least recently unloaded chunks, re-entering one skips rebuilding its data
+#[derive(Resource, Debug, Default)]
+pub struct ChunkCache {
+    entries: HashMap<IVec2, ChunkData>,
+    /// least recently inserted first
+    order: VecDeque<IVec2>,
+}
+
+impl ChunkCache {
+    fn insert(&mut self, chunk_pos: IVec2, chunk_data: ChunkData, capacity: usize) {
+        if self.entries.insert(chunk_pos, chunk_data).is_some() {
+            self.order.retain(|pos| *pos != chunk_pos);
+        }
+        self.order.push_back(chunk_pos);
+
+        while self.order.len() > capacity {
+            let Some(oldest) = self.order.pop_front() else { break };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    fn take(&mut self, chunk_pos: &IVec2) -> Option<ChunkData> {
+        let chunk_data = self.entries.remove(chunk_pos)?;
+        self.order.retain(|pos| pos != chunk_pos);
+        Some(chunk_data)
+    }
+
+    pub fn invalidate(&mut self, chunk_pos: &IVec2) {
+        self.take(chunk_pos);
+    }
+}
+
+/// sent once a chunk's tilemap entity is spawned, its tiles are in place by the next stage
+#[allow(dead_code)]
+pub struct ChunkLoaded(pub IVec2, pub Entity);
+
+/// sent when a chunk's tilemap entity gets despawned, including dirty chunks about to be rebuilt
+#[allow(dead_code)]
+pub struct ChunkUnloaded(pub IVec2);
 
 #[derive(Component)]
-pub struct GlobalTilePos(pub IVec2);
+pub struct Dirty;
 
+/// refits view load points whenever their projection changes, which covers
+/// zooming as well as window resizes since those update the projection's area
+pub fn fit_load_points_to_view(
+    tilesets: Tilesets,
+    mut view_query: Query<(&OrthographicProjection, &ViewLoadArea, &mut LoadPoint), Changed<OrthographicProjection>>,
+) {
+    let tileset = tilesets.get_by_name("world_tiles").unwrap();
+    let chunk_size = CHUNK_SIZE.as_vec2() * tileset.tile_size();
+
+    for (projection, view_load_area, mut load_point) in view_query.iter_mut() {
+        let half_size = projection.area.half_size();
+        let radius = (half_size / chunk_size).ceil().as_uvec2() + view_load_area.margin;
+        load_point.set_load_radius(radius);
+    }
+}
+
+/// despawns chunks that are past every load point's unload radius, caching their data,
+/// and drops queued ones that went out of range before being spawned
 pub fn despawn_chunks(
     mut commands: Commands,
     tilesets: Tilesets,
-    camera_query: Query<(&Transform, &LoadPoint), With<Camera>>,
-    chunks_query: Query<(Entity, &Transform), With<TileStorage>>,
-    mut rendered_chunks: ResMut<RenderedChunks>
+    config: Res<ChunkLoadingConfig>,
+    load_point_query: Query<(&Transform, &LoadPoint)>,
+    chunks_query: Query<(Entity, &ChunkPos, Option<&ChunkData>)>,
+    mut rendered_chunks: ResMut<RenderedChunks>,
+    mut pending_chunks: ResMut<PendingChunks>,
+    mut chunk_cache: ResMut<ChunkCache>,
+    mut chunk_unloaded_events: EventWriter<ChunkUnloaded>,
 ) {
     let tileset = tilesets.get_by_name("world_tiles").unwrap();
-    let (camera_transform, load_point) = camera_query.single();
-    for (chunk_entity, chunk_transform) in chunks_query.iter() {
-        if camera_transform.translation.xy().distance(chunk_transform.translation.xy()) > (load_point.radius * CHUNK_SIZE.x) as f32 * tileset.tile_size().x * 2.0 {
-            let chunk_pos = camera_pos_to_chunk_pos(chunk_transform.translation.xy(), tileset.tile_size());
-            rendered_chunks.loaded.remove(&chunk_pos);
-            commands.entity(chunk_entity).despawn_recursive();
+    let centers = load_point_centers(&load_point_query, tileset.tile_size());
+    let in_range = |chunk_pos: IVec2| {
+        centers.iter().any(|(center, load_point)| LoadPoint::covers(load_point.unload_radius, *center, chunk_pos))
+    };
+
+    for (chunk_entity, chunk_pos, chunk_data) in chunks_query.iter() {
+        if in_range(chunk_pos.0) { continue; }
+
+        if let Some(chunk_data) = chunk_data {
+            chunk_cache.insert(chunk_pos.0, chunk_data.clone(), config.cache_capacity);
         }
+        rendered_chunks.loaded.remove(&chunk_pos.0);
+        commands.entity(chunk_entity).despawn_recursive();
+        chunk_unloaded_events.send(ChunkUnloaded(chunk_pos.0));
     }
+
+    pending_chunks.tasks.retain(|chunk_pos, _| in_range(*chunk_pos));
+    pending_chunks.ready.retain(|chunk_pos, _| in_range(*chunk_pos));
 }
 
+/// starts building the missing chunks inside the union of all load points on the
+/// async compute pool, nearest first and up to `ChunkLoadingConfig::max_pending`.
+/// cached chunks skip the build and are ready right away
+pub fn queue_chunks(
+    tilesets: Tilesets,
+    world_storage: Res<WorldStorage>,
+    block_textures: Res<BlockTextures>,
+    config: Res<ChunkLoadingConfig>,
+    load_point_query: Query<(&Transform, &LoadPoint)>,
+    rendered_chunks: Res<RenderedChunks>,
+    mut pending_chunks: ResMut<PendingChunks>,
+    mut chunk_cache: ResMut<ChunkCache>,
+) {
+    let tileset = tilesets.get_by_name("world_tiles").unwrap();
+    let centers = load_point_centers(&load_point_query, tileset.tile_size());
+
+    let mut seen: HashSet<IVec2> = HashSet::new();
+    let mut missing: Vec<(i32, IVec2)> = Vec::new();
+    for (center_chunk_pos, load_point) in centers.iter() {
+        let radius = load_point.load_radius.as_ivec2();
+        for y in (center_chunk_pos.y - radius.y)..=(center_chunk_pos.y + radius.y) {
+            for x in (center_chunk_pos.x - radius.x)..=(center_chunk_pos.x + radius.x) {
+                let chunk_pos = IVec2::new(x, y);
+                if rendered_chunks.contains(&chunk_pos) || pending_chunks.contains(&chunk_pos) { continue; }
+                if config.boundary == WorldBoundary::Empty && !chunk_in_world(&world_storage, chunk_pos) { continue; }
+                if !seen.insert(chunk_pos) { continue; }
+
+                if let Some(chunk_data) = chunk_cache.take(&chunk_pos) {
+                    pending_chunks.ready.insert(chunk_pos, chunk_data);
+                    continue;
+                }
+
+                missing.push((nearest_distance(&centers, chunk_pos), chunk_pos));
+            }
+        }
+    }
+
+    missing.sort_unstable_by_key(|(distance, _)| *distance);
+
+    let task_pool = AsyncComputeTaskPool::get();
+    let free_slots = config.max_pending.saturating_sub(pending_chunks.tasks.len());
+    for (_, chunk_pos) in missing.into_iter().take(free_slots) {
+        // one extra tile around the chunk so autotiling can see across its edges
+        let region = world_storage.region(ChunkPos(chunk_pos).tile(UVec2::ZERO).0 - IVec2::ONE, CHUNK_SIZE + UVec2::splat(2));
+        let block_textures = (*block_textures).clone();
+        let boundary = config.boundary;
+        let task = task_pool.spawn(async move {
+            build_chunk_data(&region, &block_textures, chunk_pos, boundary)
+        });
+        pending_chunks.tasks.insert(chunk_pos, task);
+    }
+}
+
+/// spawns chunks whose data finished building, nearest to a
+/// load point first and no more than `ChunkLoadingConfig::spawn_budget` per frame
 pub fn spawn_chunks(
     mut commands: Commands,
     tilesets: Tilesets,
-    world_storage: Res<WorldStorage>,
-    camera_query: Query<(&Transform, &LoadPoint), With<Camera>>,
-    mut rendered_chunks: ResMut<RenderedChunks>
+    config: Res<ChunkLoadingConfig>,
+    load_point_query: Query<(&Transform, &LoadPoint)>,
+    mut rendered_chunks: ResMut<RenderedChunks>,
+    mut pending_chunks: ResMut<PendingChunks>,
+    mut chunk_loaded_events: EventWriter<ChunkLoaded>,
 ) {
     let tileset = tilesets.get_by_name("world_tiles").unwrap();
-    let (transform, load_point) = camera_query.single();
-    let camera_chunk_pos = camera_pos_to_chunk_pos(transform.translation.xy(), tileset.tile_size());
-
-    // loop through camera visible chunks (?)
-    for y in (camera_chunk_pos.y - load_point.radius as i32)..(camera_chunk_pos.y + load_point.radius as i32) {
-        for x in (camera_chunk_pos.x - load_point.radius as i32)..(camera_chunk_pos.x + load_point.radius as i32) {
-            let chunk_pos = IVec2::new(x, y);
-            if rendered_chunks.loaded.contains_key(&chunk_pos) { continue; }
-            let chunk = spawn_chunk(
-                &mut commands,
-                |x, y| world_storage.in_bounds(x, y),
-                |x, y| world_storage.get_tile(x, y) as u32,
-                tileset,
-                chunk_pos,
-                1.0,
-            );
-            rendered_chunks.loaded.insert(chunk_pos, chunk);
+    pending_chunks.poll();
+    if pending_chunks.ready.is_empty() { return; }
+
+    let centers = load_point_centers(&load_point_query, tileset.tile_size());
+    let mut ready: Vec<IVec2> = pending_chunks.ready.keys().copied().collect();
+    ready.sort_unstable_by_key(|chunk_pos| nearest_distance(&centers, *chunk_pos));
+
+    for chunk_pos in ready.into_iter().take(config.spawn_budget) {
+        let Some(chunk_data) = pending_chunks.ready.remove(&chunk_pos) else { continue };
+        let chunk = spawn_chunk(
+            &mut commands,
+            &chunk_data,
+            tileset,
+            chunk_pos,
+            1.0,
+        );
+        commands.entity(chunk).insert(chunk_data);
+        rendered_chunks.loaded.insert(chunk_pos, chunk);
+        chunk_loaded_events.send(ChunkLoaded(chunk_pos, chunk));
+    }
+}
+
+/// chunk each load point sits in
+fn load_point_centers(
+    load_point_query: &Query<(&Transform, &LoadPoint)>,
+    tile_size: Vec2,
+) -> Vec<(IVec2, LoadPoint)> {
+    load_point_query
+        .iter()
+        .map(|(transform, load_point)| (
+            WorldPos(transform.translation.xy()).to_chunk(tile_size).0,
+            *load_point,
+        ))
+        .collect()
+}
+
+fn nearest_distance(centers: &[(IVec2, LoadPoint)], chunk_pos: IVec2) -> i32 {
+    centers
+        .iter()
+        .map(|(center, _)| {
+            let offset = chunk_pos - *center;
+            offset.dot(offset)
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+fn chunk_in_world(world_storage: &WorldStorage, chunk_pos: IVec2) -> bool {
+    let min = ChunkPos(chunk_pos).tile(UVec2::ZERO).0;
+    let max = ChunkPos(chunk_pos).tile(CHUNK_SIZE).0;
+    max.x > 0 && max.y > 0 && min.x < world_storage.get_width() as i32 && min.y < world_storage.get_height() as i32
+}
+
+/// reads every tile of a chunk out of a snapshot, runs on the async compute pool
+fn build_chunk_data(
+    region: &WorldRegion,
+    block_textures: &BlockTextures,
+    chunk_pos: IVec2,
+    boundary: WorldBoundary,
+) -> ChunkData {
+    let outside = TileTexture { index: block_textures.get(Blocks::Border).base, animation: None };
+    let mut tiles = Vec::with_capacity((CHUNK_SIZE.x * CHUNK_SIZE.y) as usize);
+    for x in 0..CHUNK_SIZE.x {
+        for y in 0..CHUNK_SIZE.y {
+            let tile_pos = ChunkPos(chunk_pos).tile(uvec2(x, y)).0;
+            let tile = if region.in_bounds(tile_pos.x, tile_pos.y) {
+                Some(ChunkTile { texture: block_textures.tile_texture(region, tile_pos.x, tile_pos.y), color: Color::WHITE })
+            } else {
+                match boundary {
+                    WorldBoundary::Empty => None,
+                    WorldBoundary::Border => Some(ChunkTile { texture: outside, color: Color::WHITE }),
+                    WorldBoundary::Void => Some(ChunkTile { texture: outside, color: VOID_COLOR }),
+                }
+            };
+            tiles.push(tile);
         }
     }
+
+    ChunkData { tiles }
 }
 
-fn spawn_chunk<F, V>(
+fn spawn_chunk(
     commands: &mut Commands,
-    in_bounds: F,
-    get_content: V,
+    chunk_data: &ChunkData,
     tileset: &Tileset,
     chunk_pos: IVec2,
     chunk_z: f32,
-) -> Entity
-where
-    F: Fn(i32, i32) -> bool,
-    V: Fn(i32, i32) -> u32,
-{
+) -> Entity {
     let tilemap_entity = commands.spawn_empty().id();
     let mut tile_storage = TileStorage::empty(CHUNK_SIZE.into());
     let tile_size = tileset.tile_size();
-    let chunk_transform = Transform::from_translation(Vec3::new(
-        chunk_pos.x as f32 * CHUNK_SIZE.x as f32 * tile_size.x,
-        chunk_pos.y as f32 * CHUNK_SIZE.y as f32 * tile_size.y,
-        chunk_z,
-    ));
+    let chunk_transform = Transform::from_translation(ChunkPos(chunk_pos).origin(tile_size).0.extend(chunk_z));
 
     let tileset_handle = tileset.texture();
 
@@ -118,27 +451,21 @@ where
             for x in 0..CHUNK_SIZE.x {
                 for y in 0..CHUNK_SIZE.y {
                     let tile_pos = TilePos { x, y };
-                    let tile_pos_x = chunk_pos.x * CHUNK_SIZE.x as i32 + tile_pos.x as i32;
-                    let tile_pos_y = chunk_pos.y * CHUNK_SIZE.y as i32 + tile_pos.y as i32;
-
-                    let tile_index = if !in_bounds(tile_pos_x, tile_pos_y) {
-                        0
-                    } else {
-                        get_content(tile_pos_x, tile_pos_y)
-                    };
-
-                    let tile_entity = builder
-                        .spawn((
-                            TileBundle {
-                                position: tile_pos,
-                                texture_index: TileTextureIndex(tile_index),
-                                tilemap_id: TilemapId(builder.parent_entity()),
-                                ..default()
-                            },
-                            GlobalTilePos(ivec2(tile_pos_x, tile_pos_y))
-                        ))
-                        .id();
-                    tile_storage.set(&tile_pos, tile_entity);
+                    let Some(chunk_tile) = chunk_data.tiles[(x * CHUNK_SIZE.y + y) as usize] else { continue };
+
+                    let mut tile = builder
+                        .spawn(TileBundle {
+                            position: tile_pos,
+                            texture_index: TileTextureIndex(chunk_tile.texture.index),
+                            tilemap_id: TilemapId(builder.parent_entity()),
+                            color: TileColor(chunk_tile.color),
+                            ..default()
+                        });
+                    // animated tiles are stepped by the tilemap shader, no per-frame systems needed
+                    if let Some(animation) = chunk_tile.texture.animation {
+                        tile.insert(animation);
+                    }
+                    tile_storage.set(&tile_pos, tile.id());
                 }
             }
         })
@@ -155,76 +482,90 @@ where
         .id()
 }
 
-pub fn camera_pos_to_chunk_pos(camera_pos: Vec2, tile_size: Vec2) -> IVec2 {
-    let camera_pos = camera_pos.as_ivec2();
-    let tile_size = tile_size.as_ivec2();
-    camera_pos / (I_CHUNK_SIZE * tile_size)
-}
-
 pub fn dirty_rendered_chunk(
-    mut commands: Commands,
+    commands: &mut Commands,
     chunk_pos: &IVec2, 
-    rendered_chunks: ResMut<RenderedChunks>
+    rendered_chunks: &RenderedChunks
 ) {
     if !rendered_chunks.loaded.contains_key(chunk_pos) { return; }
     let entity = rendered_chunks.loaded.get(chunk_pos).unwrap();
     commands.entity(*entity).insert(Dirty);
 }
 
-pub fn despawn_dirty_chunks(
-    mut commands: Commands,
-    dirty_query: Query<(Entity, &ChunkPos), With<Dirty>>,
-    mut rendered_chunks: ResMut<RenderedChunks>
-) {
-    for (entity, chunk_pos) in dirty_query.iter() {
-        rendered_chunks.loaded.remove(&chunk_pos.0);
-        commands.entity(entity).despawn_recursive();
-    }
-}
+/// a single tile was edited in `WorldStorage`, rendered chunks get patched in place
+pub struct TileChanged(pub coordinates::TilePos);
 
-pub fn make_chunk_collidable(
+/// past this many changed tiles in a frame, respawning a chunk is cheaper than patching it
+const BULK_CHANGE_TILES: usize = 1024;
+
+/// retextures changed tiles and their 8 neighbours in their existing chunks,
+/// chunks with more than `BULK_CHANGE_TILES` of them get respawned through `dirty_rendered_chunk`
+pub fn update_changed_tiles(
     mut commands: Commands,
-    q: Query<(&ChunkPos, &TileStorage)>,
-    rendered_chunks: Res<RenderedChunks>,
+    mut tile_changed_events: EventReader<TileChanged>,
     world_storage: Res<WorldStorage>,
+    block_textures: Res<BlockTextures>,
+    rendered_chunks: Res<RenderedChunks>,
+    mut pending_chunks: ResMut<PendingChunks>,
+    mut chunk_cache: ResMut<ChunkCache>,
+    tile_storage_query: Query<&TileStorage>,
+    mut tile_query: Query<&mut TileTextureIndex>,
 ) {
-    for (chunk_pos, tile_storage) in q.iter() {
-        if chunk_pos.0.xy() != ivec2(0, 0) { continue; }
-        if !rendered_chunks.loaded.contains_key(&chunk_pos.0) {
-            info!("uh oh! unloaded chunk :3");
-            break;
-        }
-        for y in 0..32 {
-            for x in 0..32 {
-                if world_storage.get_tile_usize(x, y) == Blocks::Air { continue; }
-                let entity = tile_storage.get(&TilePos { x: x as u32, y: y as u32 }).unwrap();
-                commands.entity(entity).insert(Collidable);
+    let mut tiles: HashSet<IVec2> = HashSet::default();
+    for TileChanged(tile_pos) in tile_changed_events.iter() {
+        // neighbours are included so autotiled edges follow the edit
+        for y in -1..=1 {
+            for x in -1..=1 {
+                tiles.insert(tile_pos.0 + ivec2(x, y));
             }
         }
-        break;
     }
-}
-
-pub fn make_coll(
-    position: Vec2,
-    mut commands: Commands,
-    rendered_chunks: Res<RenderedChunks>,
-    world_storage: Res<WorldStorage>,
-) {
-    let chunk_pos = camera_pos_to_chunk_pos(position, vec2(8.0, 8.0));
 
-    if !rendered_chunks.loaded.contains_key(&chunk_pos) {
-        info!("tried making collisions for unrendered chunk!");
-        return;
+    let mut changed_per_chunk: HashMap<IVec2, usize> = HashMap::default();
+    for tile_pos in tiles.iter() {
+        *changed_per_chunk.entry(coordinates::TilePos(*tile_pos).to_chunk().0).or_default() += 1;
+    }
+    for (chunk_pos, changed) in changed_per_chunk.iter() {
+        if *changed > BULK_CHANGE_TILES {
+            dirty_rendered_chunk(&mut commands, chunk_pos, &rendered_chunks);
+        }
     }
 
-    let tile_storage = rendered_chunks.loaded.get(&chunk_pos);
+    for tile_pos in tiles {
+        if !world_storage.in_bounds(tile_pos.x, tile_pos.y) { continue; }
+
+        let chunk_pos = coordinates::TilePos(tile_pos).to_chunk().0;
+        // anything still being built or cached read the world before this edit
+        pending_chunks.invalidate(&chunk_pos);
+        chunk_cache.invalidate(&chunk_pos);
+        let Some(chunk_entity) = rendered_chunks.get(&chunk_pos) else { continue };
+        commands.entity(chunk_entity).remove::<ChunkData>();
+        if changed_per_chunk[&chunk_pos] > BULK_CHANGE_TILES { continue; }
+        let Ok(tile_storage) = tile_storage_query.get(chunk_entity) else { continue };
+
+        let local_pos = coordinates::TilePos(tile_pos).local();
+        let local_pos = TilePos { x: local_pos.x, y: local_pos.y };
+        let Some(tile_entity) = tile_storage.get(&local_pos) else { continue };
+        let Ok(mut texture_index) = tile_query.get_mut(tile_entity) else { continue };
 
-    for y in 0..32 {
-        for x in 0..32 {
-            if world_storage.get_tile_usize(x, y) == Blocks::Air { continue; }
-            let entity = tile_storage.get(&TilePos { x: x as u32, y: y as u32 }).unwrap();
-            commands.entity(entity).insert(Collidable);
+        let tile_texture = block_textures.tile_texture(&*world_storage, tile_pos.x, tile_pos.y);
+        texture_index.0 = tile_texture.index;
+        match tile_texture.animation {
+            Some(animation) => { commands.entity(tile_entity).insert(animation); },
+            None => { commands.entity(tile_entity).remove::<AnimatedTile>(); },
         }
     }
-}
\ No newline at end of file
+}
+
+pub fn despawn_dirty_chunks(
+    mut commands: Commands,
+    dirty_query: Query<(Entity, &ChunkPos), With<Dirty>>,
+    mut rendered_chunks: ResMut<RenderedChunks>,
+    mut chunk_unloaded_events: EventWriter<ChunkUnloaded>,
+) {
+    for (entity, chunk_pos) in dirty_query.iter() {
+        rendered_chunks.loaded.remove(&chunk_pos.0);
+        commands.entity(entity).despawn_recursive();
+        chunk_unloaded_events.send(ChunkUnloaded(chunk_pos.0));
+    }
+}
@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+
+use super::blocks::BlockCollision;
+use super::coordinates::{TilePos, WorldPos};
+use super::storage::WorldStorage;
+
+/// a collidable tile found by `overlapping_tiles`
+#[derive(Clone, Copy, Debug)]
+pub struct TileCollider {
+    pub shape: BlockCollision,
+    /// the whole tile in world space, whatever its shape
+    pub bounds: Rect,
+}
+
+impl TileCollider {
+    /// solid part of the tile in world space, slopes are sampled at world `x`
+    pub fn solid_rect(&self, x: f32) -> Option<Rect> {
+        let local_x = (x - self.bounds.min.x) / self.bounds.width();
+        let surface = self.shape.surface(local_x)?;
+        if surface <= 0.0 { return None; }
+
+        Some(Rect::new(
+            self.bounds.min.x,
+            self.bounds.min.y,
+            self.bounds.max.x,
+            self.bounds.min.y + surface * self.bounds.height(),
+        ))
+    }
+}
+
+/// every collidable tile overlapping `aabb` (in world space), read straight out of `WorldStorage`,
+/// so the cost only depends on the size of `aabb` and tiles outside the world never collide
+pub fn overlapping_tiles(
+    world: &WorldStorage,
+    aabb: Rect,
+    tile_size: Vec2,
+) -> impl Iterator<Item = TileCollider> + '_ {
+    let min = WorldPos(aabb.min).to_tile(tile_size).0;
+    let max = WorldPos(aabb.max).to_tile(tile_size).0;
+
+    (min.y..=max.y)
+        .flat_map(move |y| (min.x..=max.x).map(move |x| TilePos::new(x, y)))
+        .filter(|tile_pos| world.in_bounds(tile_pos.0.x, tile_pos.0.y))
+        .filter_map(move |tile_pos| {
+            let shape = world.get_tile(tile_pos.0.x, tile_pos.0.y).collision();
+            if !shape.is_collidable() { return None; }
+
+            let tile_min = tile_pos.min(tile_size).0;
+            Some(TileCollider {
+                shape,
+                bounds: Rect::from_corners(tile_min, tile_min + tile_size),
+            })
+        })
+}
+
+/// how far below a platform's top a body can be and still land on it
+const PLATFORM_TOLERANCE: f32 = 2.0;
+/// slack for floating point error when checking if a body was already past a surface
+const CONTACT_EPSILON: f32 = 0.01;
+
+/// which sides of a body touched a tile during its last `sweep`
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Contacts {
+    pub grounded: bool,
+    pub ceiling: bool,
+    pub wall_left: bool,
+    pub wall_right: bool,
+}
+
+impl Contacts {
+    #[inline]
+    pub fn on_wall(&self) -> bool {
+        self.wall_left || self.wall_right
+    }
+
+    /// `velocity` with whichever axes hit something zeroed, tangential movement is kept
+    pub fn clip(&self, velocity: Vec2) -> Vec2 {
+        Vec2::new(
+            if self.on_wall() { 0.0 } else { velocity.x },
+            if self.grounded || self.ceiling { 0.0 } else { velocity.y },
+        )
+    }
+}
+
+/// result of moving a body with `sweep`
+#[derive(Clone, Copy, Debug)]
+pub struct Sweep {
+    /// where the body ended up
+    pub aabb: Rect,
+    pub contacts: Contacts,
+}
+
+/// moves `aabb` by `delta` against the tiles in `world`, first along x and then along y,
+/// clamping it to whatever it hits. slopes and platforms only collide from above, so a body
+/// walks up slopes and can jump through platforms. walls up to half the body's width above its
+/// bottom get stepped onto, which is how far a body sampled at its center sits below the ground
+/// at the top of a slope
+pub fn sweep(world: &WorldStorage, aabb: Rect, delta: Vec2, tile_size: Vec2) -> Sweep {
+    let mut contacts = Contacts::default();
+    let step_height = if delta.y <= 0.0 { aabb.width() * 0.5 } else { 0.0 };
+
+    // x axis, only full height shapes act as walls
+    let mut moved = offset(aabb, Vec2::new(delta.x, 0.0));
+    for tile in overlapping_tiles(world, inflate(aabb.union(moved)), tile_size) {
+        if !matches!(tile.shape, BlockCollision::Solid | BlockCollision::HalfSlab) { continue; }
+        let Some(solid) = tile.solid_rect(tile.bounds.min.x) else { continue };
+        if solid.min.y >= aabb.max.y || solid.max.y <= aabb.min.y + step_height { continue; }
+
+        if delta.x > 0.0 && solid.min.x >= aabb.max.x - CONTACT_EPSILON && solid.min.x < moved.max.x {
+            moved = offset(moved, Vec2::new(solid.min.x - moved.max.x, 0.0));
+            contacts.wall_right = true;
+        } else if delta.x < 0.0 && solid.max.x <= aabb.min.x + CONTACT_EPSILON && solid.max.x > moved.min.x {
+            moved = offset(moved, Vec2::new(solid.max.x - moved.min.x, 0.0));
+            contacts.wall_left = true;
+        }
+    }
+
+    // y axis, sampled under the body's center so slopes act like ramps
+    let start = moved;
+    let mut moved = offset(start, Vec2::new(0.0, delta.y));
+    let center_x = start.center().x;
+    for tile in overlapping_tiles(world, inflate(start.union(moved)), tile_size) {
+        let Some(solid) = tile.solid_rect(center_x) else { continue };
+        if solid.min.x >= start.max.x || solid.max.x <= start.min.x { continue; }
+
+        if delta.y <= 0.0 {
+            let tolerance = match tile.shape {
+                BlockCollision::Platform => PLATFORM_TOLERANCE,
+                // lets a body step up a ramp it's walking into
+                BlockCollision::SlopeUp | BlockCollision::SlopeDown => tile.bounds.height(),
+                // and onto a wall the x axis let it step into
+                _ => step_height.max(CONTACT_EPSILON),
+            };
+            if solid.max.y <= start.min.y + tolerance && solid.max.y >= moved.min.y {
+                moved = offset(moved, Vec2::new(0.0, solid.max.y - moved.min.y));
+                contacts.grounded = true;
+            }
+        } else if tile.shape != BlockCollision::Platform
+            && solid.min.y >= start.max.y - CONTACT_EPSILON
+            && solid.min.y < moved.max.y {
+            moved = offset(moved, Vec2::new(0.0, solid.min.y - moved.max.y));
+            contacts.ceiling = true;
+        }
+    }
+
+    Sweep {
+        aabb: moved,
+        contacts,
+    }
+}
+
+/// grows `aabb` just enough to pick up tiles it's resting against
+#[inline]
+fn inflate(aabb: Rect) -> Rect {
+    Rect::from_corners(aabb.min - CONTACT_EPSILON, aabb.max + CONTACT_EPSILON)
+}
+
+#[inline]
+fn offset(aabb: Rect, by: Vec2) -> Rect {
+    Rect::from_corners(aabb.min + by, aabb.max + by)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::blocks::Blocks;
+
+    const TILE_SIZE: Vec2 = Vec2::new(8.0, 8.0);
+
+    /// a flat stone floor along row 0
+    fn world_with_floor() -> WorldStorage {
+        let mut world = WorldStorage::from_dimensions(8, 8);
+        for x in 0..8 {
+            world.set_tile(x, 0, Blocks::Stone);
+        }
+        world
+    }
+
+    #[test]
+    fn walks_up_a_slope_onto_the_ground_it_leads_to() {
+        let mut world = world_with_floor();
+        world.set_tile(1, 1, Blocks::StoneSlopeUp);
+        world.set_tile(2, 1, Blocks::Stone);
+        world.set_tile(3, 1, Blocks::Stone);
+
+        // standing on the floor left of the slope
+        let mut aabb = Rect::from_center_size(Vec2::new(0.0, 8.0), Vec2::new(8.0, 8.0));
+        for _ in 0..16 {
+            let sweep = sweep(&world, aabb, Vec2::new(1.0, -1.0), TILE_SIZE);
+            assert!(!sweep.contacts.on_wall(), "stopped at {:?}", sweep.aabb);
+            assert!(sweep.contacts.grounded);
+            aabb = sweep.aabb;
+        }
+
+        // on top of the tile at (2, 1)
+        assert_eq!(aabb.center().x, 16.0);
+        assert_eq!(aabb.min.y, 12.0);
+    }
+
+    #[test]
+    fn stops_at_a_wall_higher_than_a_step() {
+        let mut world = world_with_floor();
+        world.set_tile(2, 1, Blocks::Stone);
+
+        let aabb = Rect::from_center_size(Vec2::new(7.0, 8.0), Vec2::new(8.0, 8.0));
+        let sweep = sweep(&world, aabb, Vec2::new(2.0, -1.0), TILE_SIZE);
+        assert!(sweep.contacts.wall_right);
+        assert_eq!(sweep.aabb.max.x, 12.0);
+        assert_eq!(sweep.contacts.clip(Vec2::new(2.0, -1.0)).x, 0.0);
+    }
+
+    #[test]
+    fn lands_on_a_platform_and_jumps_through_it() {
+        let mut world = world_with_floor();
+        world.set_tile(1, 1, Blocks::WoodPlatform);
+
+        let falling = Rect::from_center_size(Vec2::new(8.0, 20.0), Vec2::new(8.0, 8.0));
+        let sweep_down = sweep(&world, falling, Vec2::new(0.0, -6.0), TILE_SIZE);
+        assert!(sweep_down.contacts.grounded);
+        assert_eq!(sweep_down.aabb.min.y, 12.0);
+        assert_eq!(sweep_down.contacts.clip(Vec2::new(0.0, -6.0)).y, 0.0);
+
+        let jumping = Rect::from_center_size(Vec2::new(8.0, 8.0), Vec2::new(8.0, 8.0));
+        let sweep_up = sweep(&world, jumping, Vec2::new(0.0, 6.0), TILE_SIZE);
+        assert!(!sweep_up.contacts.ceiling);
+        assert_eq!(sweep_up.aabb.min.y, 10.0);
+    }
+
+    #[test]
+    fn hits_a_ceiling() {
+        let mut world = world_with_floor();
+        world.set_tile(1, 2, Blocks::Stone);
+
+        let aabb = Rect::from_center_size(Vec2::new(8.0, 8.0), Vec2::new(8.0, 8.0));
+        let sweep = sweep(&world, aabb, Vec2::new(0.5, 6.0), TILE_SIZE);
+        assert!(sweep.contacts.ceiling);
+        assert!(!sweep.contacts.grounded);
+        assert_eq!(sweep.aabb.max.y, 12.0);
+        assert_eq!(sweep.contacts.clip(Vec2::new(0.5, 6.0)), Vec2::new(0.5, 0.0));
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+pub const CHUNK_SIZE: UVec2 = UVec2 { x: 64, y: 64 };
+pub const I_CHUNK_SIZE: IVec2 = IVec2 {
+    x: CHUNK_SIZE.x as i32,
+    y: CHUNK_SIZE.y as i32,
+};
+
+/// position in world space, in pixels
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WorldPos(pub Vec2);
+
+/// global position of a tile in `WorldStorage`, tile `(0, 0)` is centered on the world origin
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TilePos(pub IVec2);
+
+/// position of a chunk, in chunks
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ChunkPos(pub IVec2);
+
+impl WorldPos {
+    /// tile this position is inside of
+    pub fn to_tile(self, tile_size: Vec2) -> TilePos {
+        TilePos((self.0 / tile_size + 0.5).floor().as_ivec2())
+    }
+
+    pub fn to_chunk(self, tile_size: Vec2) -> ChunkPos {
+        self.to_tile(tile_size).to_chunk()
+    }
+}
+
+impl TilePos {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self(IVec2::new(x, y))
+    }
+
+    /// world position of the tile's center
+    pub fn center(self, tile_size: Vec2) -> WorldPos {
+        WorldPos(self.0.as_vec2() * tile_size)
+    }
+
+    /// world position of the tile's bottom left corner
+    pub fn min(self, tile_size: Vec2) -> WorldPos {
+        WorldPos(self.center(tile_size).0 - tile_size / 2.0)
+    }
+
+    pub fn to_chunk(self) -> ChunkPos {
+        ChunkPos(IVec2::new(
+            self.0.x.div_euclid(I_CHUNK_SIZE.x),
+            self.0.y.div_euclid(I_CHUNK_SIZE.y),
+        ))
+    }
+
+    /// position inside its chunk, always within `CHUNK_SIZE`
+    pub fn local(self) -> UVec2 {
+        UVec2::new(
+            self.0.x.rem_euclid(I_CHUNK_SIZE.x) as u32,
+            self.0.y.rem_euclid(I_CHUNK_SIZE.y) as u32,
+        )
+    }
+}
+
+impl ChunkPos {
+    /// global position of the tile at `local` inside this chunk
+    pub fn tile(self, local: UVec2) -> TilePos {
+        TilePos(self.0 * I_CHUNK_SIZE + local.as_ivec2())
+    }
+
+    /// world position the chunk's tilemap is placed at, the center of its first tile
+    pub fn origin(self, tile_size: Vec2) -> WorldPos {
+        self.tile(UVec2::ZERO).center(tile_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TILE_SIZE: Vec2 = Vec2::new(8.0, 8.0);
+
+    #[test]
+    fn world_to_tile_rounds_to_tile_centers() {
+        // tile 0 spans -4..4, edges belong to the tile above
+        assert_eq!(WorldPos(Vec2::new(-4.0, 0.0)).to_tile(TILE_SIZE), TilePos::new(0, 0));
+        assert_eq!(WorldPos(Vec2::new(-4.01, 0.0)).to_tile(TILE_SIZE), TilePos::new(-1, 0));
+        assert_eq!(WorldPos(Vec2::new(3.99, 3.99)).to_tile(TILE_SIZE), TilePos::new(0, 0));
+        assert_eq!(WorldPos(Vec2::new(4.0, -4.01)).to_tile(TILE_SIZE), TilePos::new(1, -1));
+        assert_eq!(WorldPos(Vec2::new(-12.0, -12.01)).to_tile(TILE_SIZE), TilePos::new(-1, -2));
+    }
+
+    #[test]
+    fn tile_to_chunk_floors_negatives() {
+        assert_eq!(TilePos::new(0, 0).to_chunk(), ChunkPos(IVec2::new(0, 0)));
+        assert_eq!(TilePos::new(63, 64).to_chunk(), ChunkPos(IVec2::new(0, 1)));
+        assert_eq!(TilePos::new(-1, -1).to_chunk(), ChunkPos(IVec2::new(-1, -1)));
+        assert_eq!(TilePos::new(-64, -65).to_chunk(), ChunkPos(IVec2::new(-1, -2)));
+    }
+
+    #[test]
+    fn tile_local_wraps_negatives() {
+        assert_eq!(TilePos::new(0, 63).local(), UVec2::new(0, 63));
+        assert_eq!(TilePos::new(64, 65).local(), UVec2::new(0, 1));
+        assert_eq!(TilePos::new(-1, -1).local(), UVec2::new(63, 63));
+        assert_eq!(TilePos::new(-64, -65).local(), UVec2::new(0, 63));
+    }
+
+    #[test]
+    fn chunk_and_local_round_trip() {
+        for tile in [-129, -65, -64, -63, -1, 0, 1, 63, 64, 128] {
+            let tile_pos = TilePos::new(tile, -tile);
+            assert_eq!(tile_pos.to_chunk().tile(tile_pos.local()), tile_pos);
+        }
+    }
+
+    #[test]
+    fn tile_center_and_min() {
+        assert_eq!(TilePos::new(0, 0).center(TILE_SIZE), WorldPos(Vec2::ZERO));
+        assert_eq!(TilePos::new(0, 0).min(TILE_SIZE), WorldPos(Vec2::new(-4.0, -4.0)));
+        assert_eq!(TilePos::new(-1, 2).center(TILE_SIZE), WorldPos(Vec2::new(-8.0, 16.0)));
+        assert_eq!(WorldPos(Vec2::new(-4.01, -0.01)).to_chunk(TILE_SIZE), ChunkPos(IVec2::new(-1, 0)));
+        assert_eq!(ChunkPos(IVec2::new(-1, 1)).origin(TILE_SIZE), WorldPos(Vec2::new(-512.0, 512.0)));
+    }
+}
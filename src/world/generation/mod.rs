@@ -4,6 +4,7 @@ mod stone;
 mod caves;
 mod bedrock;
 mod border;
+mod spawn;
 
 use bevy::prelude::*;
 
@@ -29,6 +30,7 @@ fn generate(mut commands: Commands) {
     caves::generate(&mut rng, &mut world);
     bedrock::generate(&mut rng, &mut world);
     // border::generate(&mut world);
+    spawn::generate(&mut world);
 
     commands.insert_resource(world);
     commands.insert_resource(NextState(Some(GameState::InGame)));
@@ -0,0 +1,14 @@
+use crate::world::WorldStorage;
+
+/// puts the spawn point on the surface in the middle of the world, on top of
+/// the highest collidable tile so there's always open air above it
+pub fn generate(world: &mut WorldStorage) {
+    let x = world.get_width() / 2;
+    let ground = (0..world.get_height())
+        .rev()
+        .find(|&y| world.get_tile_usize(x, y).collision().is_collidable())
+        .unwrap_or(0);
+    let y = (ground + 1).min(world.get_height() - 1);
+
+    world.set_spawn_point(x as u32, y as u32);
+}
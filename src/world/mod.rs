@@ -2,8 +2,13 @@ mod storage;
 pub(crate) mod chunks;
 mod generation;
 pub(crate) mod blocks;
+mod validation;
+mod autotile;
+pub(crate) mod textures;
+pub(crate) mod coordinates;
+pub(crate) mod collision;
 
-pub use chunks::LoadPoint;
+pub use chunks::{LoadPoint, ViewLoadArea};
 pub use storage::WorldStorage;
 
 use bevy::prelude::*;
@@ -34,13 +39,24 @@ impl Plugin for WorldPlugin {
         app.add_plugin(bevy_ecs_tilemap::TilemapPlugin);
         app.add_plugin(bevy_tileset::prelude::TilesetPlugin::default());
         app.add_collection_to_loading_state::<_, TileTextures>(GameState::AssetLoading);
+        app.add_system(validation::validate_tilesets.in_schedule(OnEnter(GameState::AssetValidation)));
         app.init_resource::<chunks::RenderedChunks>();        
+        app.init_resource::<chunks::ChunkLoadingConfig>();
+        app.init_resource::<chunks::PendingChunks>();
+        app.init_resource::<chunks::ChunkCache>();
+        app.add_event::<chunks::TileChanged>();
+        app.add_event::<chunks::ChunkLoaded>();
+        app.add_event::<chunks::ChunkUnloaded>();
         app.add_systems((
+            chunks::fit_load_points_to_view,
             chunks::despawn_chunks,
             chunks::despawn_dirty_chunks,
+            chunks::queue_chunks,
             chunks::spawn_chunks,
-            // chunks::make_chunk_collidable,
-        ).in_set(OnUpdate(GameState::InGame)));
+            // chunks spawned this frame need their tile storage before edits can patch them
+            apply_system_buffers,
+            chunks::update_changed_tiles,
+        ).chain().in_set(OnUpdate(GameState::InGame)));
         app.add_plugin(generation::WorldGenerationPlugin);
     }
 }
\ No newline at end of file
@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 
-use super::blocks::Blocks;
+use super::blocks::{Blocks, Walls};
+
+/// read-only tile access shared by `WorldStorage` and `WorldRegion` snapshots
+pub trait TileView {
+    fn in_bounds(&self, x: i32, y: i32) -> bool;
+    fn get_tile(&self, x: i32, y: i32) -> Blocks;
+}
 
 #[derive(Resource, Debug)]
 pub struct WorldStorage {
@@ -95,26 +101,82 @@ impl WorldStorage {
     }
 
     #[inline]
-    pub fn get_wall(&self, x: i32, y: i32) -> u32 {
+    pub fn get_wall(&self, x: i32, y: i32) -> Walls {
         assert!(x >= 0 && y >= 0);
 
         self.get_wall_idx(self.linearize(x as usize, y as usize))
     }
 
     #[inline]
-    pub fn get_wall_idx(&self, idx: usize) -> u32 {
-        self.walls[idx]
+    pub fn get_wall_idx(&self, idx: usize) -> Walls {
+        Walls::from(self.walls[idx])
     }
 
     #[inline]
-    pub fn set_wall(&mut self, x: i32, y: i32, tile: u32) {
+    pub fn set_wall(&mut self, x: i32, y: i32, wall: Walls) {
         assert!(x >= 0 && y >= 0);
 
-        self.set_wall_idx(self.linearize(x as usize, y as usize), tile);
+        self.set_wall_idx(self.linearize(x as usize, y as usize), wall as u32);
     }
 
     #[inline]
     pub fn set_wall_idx(&mut self, idx: usize, wall: u32) {
         self.walls[idx] = wall;
     }
-}
\ No newline at end of file
+
+    /// copies the tiles in `min..min + size`, so they can be read off the main thread
+    pub fn region(&self, min: IVec2, size: UVec2) -> WorldRegion {
+        let mut tiles = Vec::with_capacity((size.x * size.y) as usize);
+        for y in min.y..min.y + size.y as i32 {
+            for x in min.x..min.x + size.x as i32 {
+                tiles.push(if self.in_bounds(x, y) { Some(self.get_tile(x, y)) } else { None });
+            }
+        }
+
+        WorldRegion { min, size, tiles }
+    }
+}
+
+impl TileView for WorldStorage {
+    #[inline]
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        WorldStorage::in_bounds(self, x, y)
+    }
+
+    #[inline]
+    fn get_tile(&self, x: i32, y: i32) -> Blocks {
+        WorldStorage::get_tile(self, x, y)
+    }
+}
+
+/// owned snapshot of a rectangle of `WorldStorage`, made with `WorldStorage::region`
+#[derive(Debug, Clone)]
+pub struct WorldRegion {
+    min: IVec2,
+    size: UVec2,
+    /// `None` where the region pokes out of the world
+    tiles: Vec<Option<Blocks>>,
+}
+
+impl WorldRegion {
+    #[inline]
+    fn get(&self, x: i32, y: i32) -> Option<Blocks> {
+        let local = IVec2::new(x, y) - self.min;
+        if local.x < 0 || local.y < 0 || local.x >= self.size.x as i32 || local.y >= self.size.y as i32 {
+            return None;
+        }
+        self.tiles[local.x as usize + self.size.x as usize * local.y as usize]
+    }
+}
+
+impl TileView for WorldRegion {
+    #[inline]
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        self.get(x, y).is_some()
+    }
+
+    #[inline]
+    fn get_tile(&self, x: i32, y: i32) -> Blocks {
+        self.get(x, y).unwrap_or(Blocks::Air)
+    }
+}
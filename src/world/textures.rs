@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::tiles::AnimatedTile;
+use bevy_tileset::prelude::*;
+
+use super::autotile;
+use super::blocks::Blocks;
+use super::storage::TileView;
+
+/// atlas indices a single block renders with
+#[derive(Debug, Clone, Default)]
+pub struct BlockTexture {
+    pub base: u32,
+    /// `base` followed by the block's declared variants
+    pub variants: Vec<u32>,
+    /// one entry per autotile shape before `autotile::INTERIOR`, `None` where the tileset has no texture for it
+    pub shapes: Vec<Option<u32>>,
+    /// set when the block's tile is `Animated` in its `.ron`, overrides shapes and variants
+    pub animation: Option<AnimatedTile>,
+}
+
+/// what a single tile entity gets rendered with
+#[derive(Debug, Clone, Copy)]
+pub struct TileTexture {
+    pub index: u32,
+    pub animation: Option<AnimatedTile>,
+}
+
+/// block textures resolved by name from `world_tiles.ron`, so chunks
+/// don't have to assume block ids and atlas indices line up
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BlockTextures {
+    blocks: Vec<BlockTexture>,
+}
+
+impl BlockTextures {
+    pub fn from_tileset(tileset: &Tileset) -> Self {
+        let blocks = Blocks::ALL
+            .iter()
+            .map(|block| {
+                let name = block.tile_name();
+                let shapes = if block.autotiles() {
+                    (0..autotile::INTERIOR)
+                        .map(|shape| standard_index(tileset, &format!("{name}_{shape}")))
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
+                let (base, animation) = match tileset.get_tile_index(name) {
+                    Some(TileIndex::Standard(index)) => (index as u32, None),
+                    Some(TileIndex::Animated(start, end, speed)) => (start as u32, Some(AnimatedTile {
+                        start: start as u32,
+                        end: end as u32,
+                        speed,
+                    })),
+                    None => (0, None),
+                };
+                let variants = std::iter::once(base)
+                    .chain(block.variants().iter().filter_map(|variant| standard_index(tileset, variant)))
+                    .collect();
+
+                BlockTexture {
+                    base,
+                    variants,
+                    shapes,
+                    animation,
+                }
+            })
+            .collect();
+
+        Self { blocks }
+    }
+
+    #[inline]
+    pub fn get(&self, block: Blocks) -> &BlockTexture {
+        &self.blocks[block as usize]
+    }
+
+    /// every atlas index some block renders with
+    pub fn atlas_indices(&self) -> impl Iterator<Item = u32> + '_ {
+        self.blocks
+            .iter()
+            .flat_map(|texture| {
+                let frames = texture.animation.map_or(0..0, |animation| animation.start..animation.end + 1);
+                texture.variants.iter().chain(texture.shapes.iter().flatten()).copied().chain(frames)
+            })
+    }
+
+    /// texture for the tile at `x`, `y`, animated blocks play their frames,
+    /// everything else goes through `texture_index`
+    pub fn tile_texture<W: TileView>(&self, world: &W, x: i32, y: i32) -> TileTexture {
+        let texture = self.get(world.get_tile(x, y));
+        match texture.animation {
+            Some(animation) => TileTexture { index: animation.start, animation: Some(animation) },
+            None => TileTexture { index: self.texture_index(world, x, y), animation: None },
+        }
+    }
+
+    /// texture index for the tile at `x`, `y`, picking the autotile shape from its neighbours
+    /// and falling back to a variant chosen by position
+    pub fn texture_index<W: TileView>(&self, world: &W, x: i32, y: i32) -> u32 {
+        let block = world.get_tile(x, y);
+        let texture = self.get(block);
+        let variant = texture.variants[position_hash(x, y) as usize % texture.variants.len()];
+        if texture.shapes.is_empty() {
+            return variant;
+        }
+
+        let mask = autotile::neighbour_mask(IVec2::new(x, y), |pos| {
+            // connect to the world edge so it doesn't outline the map
+            !world.in_bounds(pos.x, pos.y) || world.get_tile(pos.x, pos.y) != Blocks::Air
+        });
+
+        // the interior has no entry, it's one of the block's own variants
+        texture.shapes.get(autotile::shape(mask)).copied().flatten().unwrap_or(variant)
+    }
+}
+
+/// cheap integer hash of a tile position, stable across chunk reloads and sessions
+#[inline]
+fn position_hash(x: i32, y: i32) -> u32 {
+    let mut hash = (x as u32).wrapping_mul(0x9e37_79b1) ^ (y as u32).wrapping_mul(0x85eb_ca77);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2c1b_3c6d);
+    hash ^= hash >> 12;
+    hash
+}
+
+pub(super) fn standard_index(tileset: &Tileset, name: &str) -> Option<u32> {
+    match tileset.get_tile_index(name) {
+        Some(TileIndex::Standard(index)) => Some(index as u32),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_hash_is_deterministic() {
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (-1, -1), (1023, 255), (i32::MIN, i32::MAX)] {
+            assert_eq!(position_hash(x, y), position_hash(x, y));
+        }
+        assert_ne!(position_hash(1, 0), position_hash(0, 1));
+    }
+
+    #[test]
+    fn position_hash_spreads_across_variants() {
+        for variant_count in 2..=4 {
+            let mut counts = vec![0; variant_count];
+            for y in -32..32 {
+                for x in -32..32 {
+                    counts[position_hash(x, y) as usize % variant_count] += 1;
+                }
+            }
+
+            // every variant gets within 20% of an even share
+            let even = 64 * 64 / variant_count;
+            for count in counts {
+                assert!(count * 5 > even * 4 && count * 5 < even * 6, "{count} of {even} in a {variant_count} way split");
+            }
+        }
+    }
+}
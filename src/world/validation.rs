@@ -0,0 +1,179 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_tileset::prelude::*;
+
+use crate::states::GameState;
+use super::blocks::{Blocks, Walls};
+use super::autotile;
+use super::textures::{self, BlockTextures};
+
+/// everything that didn't line up between the block registry and the tilesets
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    fn error(&mut self, message: String) {
+        self.errors.push(message);
+    }
+
+    fn warn(&mut self, message: String) {
+        self.warnings.push(message);
+    }
+
+    pub fn is_fatal(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    fn log(&self) {
+        for warning in self.warnings.iter() {
+            warn!("tileset validation: {warning}");
+        }
+        for error in self.errors.iter() {
+            error!("tileset validation: {error}");
+        }
+    }
+}
+
+/// runs once every asset is loaded, only moves on to world generation
+/// if the block and wall ids agree with `world_tiles.ron` and `world_walls.ron`
+pub fn validate_tilesets(
+    mut commands: Commands,
+    tilesets: Tilesets,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let mut report = ValidationReport::default();
+
+    let block_textures = tilesets.get_by_name("world_tiles").map(|tileset| {
+        let block_textures = BlockTextures::from_tileset(tileset);
+        check_unclaimed(&mut report, tileset, "world_tiles", block_textures.atlas_indices());
+        block_textures
+    });
+    check_registry(
+        &mut report,
+        tilesets.get_by_name("world_tiles"),
+        "world_tiles",
+        Blocks::ALL.iter().map(|block| (*block as u32, block.tile_name())),
+    );
+    if let Some(tileset) = tilesets.get_by_name("world_tiles") {
+        check_variants(&mut report, tileset);
+        check_shapes(&mut report, tileset);
+    }
+
+    if let Some(tileset) = tilesets.get_by_name("world_walls") {
+        let wall_indices = Walls::ALL
+            .iter()
+            .filter_map(|wall| textures::standard_index(tileset, wall.tile_name()));
+        check_unclaimed(&mut report, tileset, "world_walls", wall_indices);
+    }
+    check_registry(
+        &mut report,
+        tilesets.get_by_name("world_walls"),
+        "world_walls",
+        Walls::ALL.iter().map(|wall| (*wall as u32, wall.tile_name())),
+    );
+
+    report.log();
+
+    if report.is_fatal() {
+        error!(
+            "found {} fatal tileset mismatch(es), refusing to generate world",
+            report.errors.len()
+        );
+        return;
+    }
+
+    info!("tilesets validated with {} warning(s)", report.warnings.len());
+    if let Some(block_textures) = block_textures {
+        commands.insert_resource(block_textures);
+    }
+    next_state.set(GameState::WorldGeneration);
+}
+
+fn check_registry<I>(
+    report: &mut ValidationReport,
+    tileset: Option<&Tileset>,
+    tileset_name: &str,
+    entries: I,
+)
+where
+    I: Iterator<Item = (u32, &'static str)>,
+{
+    let Some(tileset) = tileset else {
+        report.error(format!("tileset `{tileset_name}` is not loaded"));
+        return;
+    };
+
+    for (id, name) in entries {
+        match tileset.get_tile_name(&(id as usize)) {
+            Some(tile_name) if tile_name == name => {},
+            Some(tile_name) => report.error(format!(
+                "`{tileset_name}` id {id} is `{tile_name}`, but the registry expects `{name}`"
+            )),
+            None => report.error(format!(
+                "`{tileset_name}` has no tile with id {id}, the registry expects `{name}`"
+            )),
+        }
+
+        if tileset.get_tile_index(name).is_none() {
+            report.error(format!(
+                "`{tileset_name}` has no tile named `{name}` for id {id}"
+            ));
+        }
+    }
+}
+
+fn check_variants(report: &mut ValidationReport, tileset: &Tileset) {
+    for block in Blocks::ALL.iter() {
+        for variant in block.variants().iter() {
+            if textures::standard_index(tileset, variant).is_some() { continue; }
+            report.error(format!(
+                "`world_tiles` has no standard tile named `{variant}`, declared as a variant of {block:?}"
+            ));
+        }
+    }
+}
+
+/// warns about autotiling blocks missing shape textures, those shapes render with the base tile.
+/// see `assets/tiles/AUTOTILE.md` for what each shape looks like
+fn check_shapes(report: &mut ValidationReport, tileset: &Tileset) {
+    for block in Blocks::ALL.iter().filter(|block| block.autotiles()) {
+        let name = block.tile_name();
+        let missing: Vec<String> = (0..autotile::INTERIOR)
+            .map(|shape| format!("{name}_{shape}"))
+            .filter(|shape_name| textures::standard_index(tileset, shape_name).is_none())
+            .collect();
+
+        if missing.len() == autotile::INTERIOR {
+            report.warn(format!(
+                "{block:?} autotiles, but `world_tiles` has none of its shape tiles `{name}_0` to `{name}_{}`",
+                autotile::INTERIOR - 1
+            ));
+        } else if !missing.is_empty() {
+            report.warn(format!(
+                "{block:?} autotiles, but `world_tiles` is missing its shape tiles {}",
+                missing.iter().map(|shape_name| format!("`{shape_name}`")).collect::<Vec<_>>().join(", ")
+            ));
+        }
+    }
+}
+
+/// warns about atlas textures nothing in the registry renders with
+fn check_unclaimed<I>(
+    report: &mut ValidationReport,
+    tileset: &Tileset,
+    tileset_name: &str,
+    claimed: I,
+)
+where
+    I: Iterator<Item = u32>,
+{
+    let claimed: HashSet<u32> = claimed.collect();
+    for index in 0..tileset.atlas().len() as u32 {
+        if claimed.contains(&index) { continue; }
+        report.warn(format!(
+            "`{tileset_name}` atlas index {index} has no matching registry entry"
+        ));
+    }
+}